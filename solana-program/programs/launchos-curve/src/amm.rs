@@ -0,0 +1,186 @@
+//! Constant-product (x * y = k) pool math for graduated tokens
+//! Reserves are SOL (lamports) and token base units; LP supply is in LP base units
+//! Rounding always favours the pool so k never decreases
+
+use anchor_lang::prelude::*;
+use crate::errors::CurveError;
+
+/// Swap fee kept in the pool for liquidity providers
+pub const POOL_FEE_BPS: u128 = 30; // 0.3%
+pub const FEE_DENOMINATOR: u128 = 10000;
+
+/// Integer square root (floor)
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from an upper bound, converges monotonically down
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) >> 1;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// LP minted when seeding a pool: sqrt(sol * token)
+pub fn calculate_initial_liquidity(sol_amount: u64, token_amount: u64) -> Result<u64> {
+    let product = (sol_amount as u128)
+        .checked_mul(token_amount as u128)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?;
+    let liquidity = isqrt(product);
+    require!(liquidity > 0, CurveError::InsufficientLiquidity);
+    Ok(liquidity as u64)
+}
+
+/// Output of an exact-in swap after the pool fee
+/// out = in * (1 - fee) * reserve_out / (reserve_in + in * (1 - fee))
+pub fn calculate_swap_output(amount_in: u64, reserve_in: u64, reserve_out: u64) -> Result<u64> {
+    require!(reserve_in > 0 && reserve_out > 0, CurveError::InsufficientLiquidity);
+
+    let amount_in_with_fee = (amount_in as u128)
+        .checked_mul(FEE_DENOMINATOR - POOL_FEE_BPS)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?;
+
+    let numerator = amount_in_with_fee
+        .checked_mul(reserve_out as u128)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?;
+
+    let denominator = (reserve_in as u128)
+        .checked_mul(FEE_DENOMINATOR)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?
+        .checked_add(amount_in_with_fee)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?;
+
+    // Strictly below reserve_out, so the pool can never be drained
+    Ok((numerator / denominator) as u64)
+}
+
+/// Token deposit and LP minted for adding `sol_amount` at the current ratio
+/// Token side rounds up, LP rounds down
+pub fn calculate_deposit(
+    sol_amount: u64,
+    sol_reserve: u64,
+    token_reserve: u64,
+    lp_supply: u64,
+) -> Result<(u64, u64)> {
+    require!(sol_reserve > 0 && lp_supply > 0, CurveError::InsufficientLiquidity);
+
+    let token_numerator = (sol_amount as u128)
+        .checked_mul(token_reserve as u128)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?;
+    let token_amount = token_numerator.div_ceil(sol_reserve as u128);
+
+    let lp_amount = (sol_amount as u128)
+        .checked_mul(lp_supply as u128)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?
+        / sol_reserve as u128;
+
+    Ok((
+        u64::try_from(token_amount).map_err(|_| error!(CurveError::ArithmeticOverflow))?,
+        u64::try_from(lp_amount).map_err(|_| error!(CurveError::ArithmeticOverflow))?,
+    ))
+}
+
+/// SOL and tokens returned for burning `lp_amount` (both round down)
+pub fn calculate_withdrawal(
+    lp_amount: u64,
+    sol_reserve: u64,
+    token_reserve: u64,
+    lp_supply: u64,
+) -> Result<(u64, u64)> {
+    require!(lp_amount <= lp_supply, CurveError::InsufficientLiquidity);
+
+    let sol_amount = (lp_amount as u128)
+        .checked_mul(sol_reserve as u128)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?
+        / lp_supply as u128;
+
+    let token_amount = (lp_amount as u128)
+        .checked_mul(token_reserve as u128)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?
+        / lp_supply as u128;
+
+    Ok((sol_amount as u64, token_amount as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOL: u64 = 8_000_000_000; // 8 SOL seed
+    const TOKENS: u64 = 200_000_000_000_000; // 200M tokens (6 decimals)
+
+    #[test]
+    fn test_isqrt() {
+        for n in [0u128, 1, 2, 3, 4, 15, 16, 17, 1 << 64, u64::MAX as u128, u128::MAX] {
+            let r = isqrt(n);
+            assert!(r * r <= n, "n = {}", n);
+            if let Some(sq) = (r + 1).checked_mul(r + 1) {
+                assert!(sq > n, "n = {}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_initial_liquidity() {
+        assert_eq!(calculate_initial_liquidity(4, 9).unwrap(), 6);
+        assert_eq!(
+            calculate_initial_liquidity(SOL, TOKENS).unwrap(),
+            isqrt(SOL as u128 * TOKENS as u128) as u64
+        );
+        assert!(calculate_initial_liquidity(0, TOKENS).is_err());
+    }
+
+    #[test]
+    fn test_swap_keeps_k_and_charges_fee() {
+        let amount_in = 1_000_000_000; // 1 SOL
+        let out = calculate_swap_output(amount_in, SOL, TOKENS).unwrap();
+
+        // k never decreases
+        let k_before = SOL as u128 * TOKENS as u128;
+        let k_after = (SOL + amount_in) as u128 * (TOKENS - out) as u128;
+        assert!(k_after >= k_before);
+
+        // Fee-free output would be strictly larger
+        let no_fee = (amount_in as u128 * TOKENS as u128 / (SOL + amount_in) as u128) as u64;
+        assert!(out < no_fee);
+        assert!(out > no_fee * 9960 / 10000);
+    }
+
+    #[test]
+    fn test_swap_cannot_drain_pool() {
+        let out = calculate_swap_output(u32::MAX as u64 * 1000, SOL, TOKENS).unwrap();
+        assert!(out < TOKENS);
+        assert_eq!(calculate_swap_output(0, SOL, TOKENS).unwrap(), 0);
+        assert!(calculate_swap_output(1, 0, TOKENS).is_err());
+    }
+
+    #[test]
+    fn test_deposit_then_withdraw_is_not_profitable() {
+        let lp_supply = calculate_initial_liquidity(SOL, TOKENS).unwrap();
+        let sol_in = 1_234_567_891;
+
+        let (token_in, lp_out) = calculate_deposit(sol_in, SOL, TOKENS, lp_supply).unwrap();
+        assert!(lp_out > 0);
+
+        let (sol_out, token_out) = calculate_withdrawal(
+            lp_out,
+            SOL + sol_in,
+            TOKENS + token_in,
+            lp_supply + lp_out,
+        )
+        .unwrap();
+        assert!(sol_out <= sol_in);
+        assert!(token_out <= token_in);
+    }
+
+    #[test]
+    fn test_withdrawal_bounds() {
+        let (sol, tokens) = calculate_withdrawal(50, 1_000, 2_000, 100).unwrap();
+        assert_eq!((sol, tokens), (500, 1_000));
+        assert!(calculate_withdrawal(101, 1_000, 2_000, 100).is_err());
+    }
+}
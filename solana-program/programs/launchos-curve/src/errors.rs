@@ -32,6 +32,32 @@ pub enum CurveError {
     #[msg("This account is banned for bot activity")]
     AccountBanned,
 
+    #[msg("Account is not on the legacy ban list")]
+    NotBanned,
+
+    // Bot Reports
+    #[msg("Account is flagged by bot reports pending admin review")]
+    AccountFlagged,
+
+    #[msg("Cannot report yourself")]
+    SelfReport,
+
+    #[msg("Reporter already reported this account")]
+    DuplicateReport,
+
+    #[msg("Report has the maximum number of reporters")]
+    ReportFull,
+
+    #[msg("Remaining accounts must be the reporters, in report order")]
+    InvalidReporterAccounts,
+
+    // Curve Access Lists
+    #[msg("Wallet is denied on this curve by its creator")]
+    CurveAccessDenied,
+
+    #[msg("Curve is in an allowlist-only phase and this wallet is not allowed")]
+    NotOnAllowList,
+
     #[msg("Invalid curve status transition")]
     InvalidStatusTransition,
 
@@ -57,18 +83,80 @@ pub enum CurveError {
     #[msg("Launch time not reached yet")]
     LaunchTimeNotReached,
 
+    #[msg("Legacy freeze trigger is not recognized")]
+    InvalidFreezeTrigger,
+
+    // Cancellation
+    #[msg("Remaining accounts must be this curve's access list, access entries or referral codes, each code followed by its owner")]
+    InvalidCancelAccounts,
+
+    // Refunds
+    #[msg("Refund delay after freeze has not passed")]
+    RefundDeadlineNotReached,
+
+    #[msg("Curve is not in refund mode")]
+    RefundNotActive,
+
     #[msg("Snapshot not created yet")]
     SnapshotNotCreated,
 
     #[msg("Snapshot already exists")]
     SnapshotAlreadyExists,
 
+    #[msg("Registered holders do not add up to supply_at_freeze")]
+    SnapshotIncomplete,
+
+    #[msg("Snapshot tree is full")]
+    SnapshotTreeFull,
+
+    #[msg("An on-chain snapshot is already being built for this curve")]
+    SnapshotInProgress,
+
+    // Snapshot Challenges
+    #[msg("Snapshot is not open for challenges")]
+    SnapshotNotChallengeable,
+
+    #[msg("Snapshot challenge window has not passed or disputes are open")]
+    SnapshotNotSettled,
+
+    #[msg("Leaf amount matches the holder's keys")]
+    NoAmountMismatch,
+
+    #[msg("Dispute does not target the current snapshot")]
+    DisputeStale,
+
+    #[msg("Dispute response period has not passed")]
+    DisputeResponsePending,
+
     #[msg("Curve already launched")]
     AlreadyLaunched,
 
     #[msg("Invalid Merkle proof")]
     InvalidMerkleProof,
 
+    // Airdrop Vault
+    #[msg("Curve has not launched yet")]
+    CurveNotLaunched,
+
+    #[msg("Token mint is already set for this curve")]
+    TokenMintAlreadySet,
+
+    #[msg("Airdrop vault is already funded")]
+    AirdropAlreadyFunded,
+
+    #[msg("Airdrop vault is not funded yet")]
+    AirdropNotFunded,
+
+    #[msg("Token metadata: name 1-32, symbol 1-10, uri up to 200 bytes")]
+    InvalidTokenMetadata,
+
+    // Liquidity Pool
+    #[msg("Pool needs a launch_with_mint launch with SOL and tokens in its vaults")]
+    InsufficientLiquidity,
+
+    #[msg("Pool output below minimum or deposit above maximum (price moved)")]
+    PoolSlippageExceeded,
+
     #[msg("Tokens already claimed")]
     AlreadyClaimed,
 
@@ -83,4 +171,84 @@ pub enum CurveError {
 
     #[msg("Trading is disabled in this state")]
     TradingDisabled,
+
+    // Slippage Protection
+    #[msg("Total cost exceeds max_total_cost (price moved)")]
+    SlippageExceeded,
+
+    #[msg("Seller payout is below min_payout (price moved)")]
+    PayoutBelowMinimum,
+
+    #[msg("Transaction deadline has passed")]
+    DeadlineExceeded,
+
+    #[msg("Budget buys fewer keys than min_keys")]
+    BelowMinKeys,
+
+    // Referral Registry
+    #[msg("Referral code must be 3-16 letters, digits, '_' or '-'")]
+    InvalidReferralCode,
+
+    #[msg("Referral code does not match the holder's bound referrer")]
+    ReferrerMismatch,
+
+    // Fee Schedule
+    #[msg("Fee schedule buckets must sum to 10000 bps")]
+    InvalidFeeSchedule,
+
+    // Fee Vault
+    #[msg("No fees to claim")]
+    NothingToClaim,
+
+    #[msg("Referral fees are claimed with claim_referral_fees")]
+    InvalidFeeRecipient,
+
+    // Pause
+    #[msg("Trading is paused")]
+    TradingPaused,
+
+    #[msg("Curve creation is paused")]
+    CreationPaused,
+
+    #[msg("Launches are paused")]
+    LaunchesPaused,
+
+    #[msg("Claims are paused")]
+    ClaimsPaused,
+
+    #[msg("This curve is paused by the admin")]
+    CurvePaused,
+
+    // Admin Config
+    #[msg("Invalid config: limits must be positive and creator_min_buy <= max_purchase")]
+    InvalidConfig,
+
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+
+    #[msg("Timelock delay has not elapsed")]
+    TimelockNotElapsed,
+
+    // Multisig
+    #[msg("Multisig needs 1-10 unique signers and a threshold between 1 and the signer count")]
+    InvalidMultisigSigners,
+
+    #[msg("Signer is not a member of this multisig")]
+    NotMultisigSigner,
+
+    #[msg("Proposal does not have enough approvals")]
+    MultisigThresholdNotMet,
+
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+
+    #[msg("Multisig signers changed since this proposal was made")]
+    ProposalStale,
+
+    #[msg("Proposal exceeds 16 accounts or 512 bytes of data, or requires an outside signer")]
+    InvalidProposal,
+
+    // Account Migrations
+    #[msg("Account is already in the current layout")]
+    AccountAlreadyMigrated,
 }
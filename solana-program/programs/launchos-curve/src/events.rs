@@ -1,4 +1,9 @@
 use anchor_lang::prelude::*;
+use crate::math_v6::{ReferrerType, V6BuyFeeDistribution, V6SellFeeDistribution};
+use crate::state::{
+    AdminActionKind, BanReason, ConfigParams, FeeRecipient, FeeSchedule, FreezeTrigger,
+    ReportReason, SnapshotChallenge,
+};
 
 /// Event emitted when a curve is frozen
 #[event]
@@ -7,7 +12,7 @@ pub struct CurveFrozenEvent {
     pub curve_id: Pubkey,
 
     /// Trigger that caused the freeze
-    pub trigger: String,
+    pub trigger: FreezeTrigger,
 
     /// Supply at time of freeze
     pub supply: u128,
@@ -19,6 +24,63 @@ pub struct CurveFrozenEvent {
     pub timestamp: i64,
 }
 
+/// Event emitted when a creator cancels a Pending curve, freeing its handle
+#[event]
+pub struct CurveCancelledEvent {
+    /// Curve ID (closed)
+    pub curve: Pubkey,
+
+    /// Creator who cancelled and was refunded
+    pub creator: Pubkey,
+
+    /// Handle freed for a new curve
+    pub twitter_handle: String,
+
+    /// Reserve contribution refunded to the creator (lamports, excluding rent)
+    pub refunded: u128,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when a frozen curve that never launched enters refund mode
+#[event]
+pub struct CurveRefundingEvent {
+    /// Curve ID
+    pub curve: Pubkey,
+
+    /// Wallet that started the refund
+    pub started_by: Pubkey,
+
+    /// True if started early by the admin rather than after the refund delay
+    pub by_admin: bool,
+
+    /// Reserve redeemable pro-rata by holders (lamports)
+    pub reserve: u128,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when a holder redeems keys from a refunding curve
+#[event]
+pub struct RefundClaimedEvent {
+    /// Curve ID
+    pub curve: Pubkey,
+
+    /// Holder who was refunded
+    pub holder: Pubkey,
+
+    /// Keys redeemed
+    pub keys: u64,
+
+    /// Refund paid (lamports, excluding returned rent)
+    pub amount: u128,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
 /// Event emitted when a snapshot is created
 #[event]
 pub struct SnapshotCreatedEvent {
@@ -41,6 +103,80 @@ pub struct SnapshotCreatedEvent {
     pub timestamp: i64,
 }
 
+/// Event emitted when a KeyHolder is appended to an on-chain snapshot tree
+/// Indexers replay these in leaf_index order to build claim proofs
+#[event]
+pub struct SnapshotHolderRegisteredEvent {
+    /// Curve ID
+    pub curve: Pubkey,
+
+    /// Holder registered
+    pub holder: Pubkey,
+
+    /// Keys held at freeze
+    pub amount: u64,
+
+    /// Position of the holder's leaf in the tree
+    pub leaf_index: u32,
+
+    /// Root after this holder was appended
+    pub root: [u8; 32],
+}
+
+/// Event emitted when a holder disputes their absence from a snapshot root
+#[event]
+pub struct SnapshotDisputeOpenedEvent {
+    /// Curve ID
+    pub curve: Pubkey,
+
+    /// Holder said to be missing
+    pub holder: Pubkey,
+
+    /// Wallet that staked the dispute
+    pub challenger: Pubkey,
+
+    /// Deadline for a membership proof before the dispute can be upheld
+    pub respond_by: i64,
+}
+
+/// Event emitted when a membership dispute is closed
+#[event]
+pub struct SnapshotDisputeResolvedEvent {
+    /// Curve ID
+    pub curve: Pubkey,
+
+    /// Holder the dispute was about
+    pub holder: Pubkey,
+
+    /// True if upheld or stale (stake refunded), false if answered (stake to creator)
+    pub upheld: bool,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when a creator-submitted snapshot root is invalidated
+#[event]
+pub struct SnapshotInvalidatedEvent {
+    /// Curve ID
+    pub curve: Pubkey,
+
+    /// Root that was thrown out
+    pub merkle_root: [u8; 32],
+
+    /// Grounds for invalidation
+    pub challenge: SnapshotChallenge,
+
+    /// Holder whose leaf (or absence) proved the root wrong
+    pub holder: Pubkey,
+
+    /// Wallet that receives the snapshot's rent
+    pub challenger: Pubkey,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
 /// Event emitted when a curve is launched
 #[event]
 pub struct CurveLaunchedEvent {
@@ -92,8 +228,11 @@ pub struct KeysPurchasedEvent {
     /// Referrer (if any)
     pub referrer: Option<Pubkey>,
 
-    /// Amount earned by referrer (or creator if no referrer)
-    pub instant_fee_paid: u128,
+    /// V6 fee routing applied to this trade
+    pub referrer_type: ReferrerType,
+
+    /// Fee buckets as routed (reserve, referral, project, buyback, community)
+    pub fees: V6BuyFeeDistribution,
 }
 
 /// Event emitted when keys are sold
@@ -113,6 +252,156 @@ pub struct KeysSoldEvent {
 
     /// Gross return before fees
     pub gross_return: u128,
+
+    /// Referrer (if any)
+    pub referrer: Option<Pubkey>,
+
+    /// V6 fee routing applied to this trade
+    pub referrer_type: ReferrerType,
+
+    /// Fee buckets as routed (seller, referral, project, buyback, community)
+    pub fees: V6SellFeeDistribution,
+}
+
+/// Event emitted when a referral code is registered
+#[event]
+pub struct ReferrerRegisteredEvent {
+    /// Curve ID
+    pub curve: Pubkey,
+
+    /// Referral code account
+    pub referrer: Pubkey,
+
+    /// Owner wallet that receives referral fees
+    pub owner: Pubkey,
+
+    /// Referral code
+    pub code: String,
+}
+
+/// Event emitted when the airdrop vault is funded for holder claims
+#[event]
+pub struct AirdropFundedEvent {
+    /// Curve ID
+    pub curve: Pubkey,
+
+    /// Snapshot that owns the vault
+    pub snapshot: Pubkey,
+
+    /// Token being airdropped
+    pub token_mint: Pubkey,
+
+    /// Tokens deposited (the snapshot's total_token_pool)
+    pub amount: u64,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when launch_with_mint mints the fixed supply
+#[event]
+pub struct TokenMintedEvent {
+    /// Curve ID
+    pub curve: Pubkey,
+
+    /// Mint created by the program
+    pub token_mint: Pubkey,
+
+    /// Total supply minted (mint authority revoked afterwards)
+    pub total_supply: u64,
+
+    /// Tokens sent to the airdrop vault
+    pub airdrop_amount: u64,
+
+    /// Tokens sent to the liquidity vault
+    pub liquidity_amount: u64,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when a launched curve's liquidity pool is seeded
+#[event]
+pub struct PoolCreatedEvent {
+    /// Curve ID
+    pub curve: Pubkey,
+
+    /// Pool PDA
+    pub pool: Pubkey,
+
+    /// Launched token
+    pub token_mint: Pubkey,
+
+    /// LP token mint
+    pub lp_mint: Pubkey,
+
+    /// Seed SOL (the curve's initial_buy_amount)
+    pub sol_reserve: u64,
+
+    /// Seed tokens (the launch liquidity allocation)
+    pub token_reserve: u64,
+
+    /// Seed LP, permanently locked
+    pub locked_liquidity: u64,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted on every pool swap
+#[event]
+pub struct PoolSwapEvent {
+    /// Pool PDA
+    pub pool: Pubkey,
+
+    /// Trader
+    pub user: Pubkey,
+
+    /// True if SOL was swapped for tokens
+    pub sol_to_token: bool,
+
+    /// Amount paid in
+    pub amount_in: u64,
+
+    /// Amount received
+    pub amount_out: u64,
+
+    /// SOL reserve after the swap
+    pub sol_reserve: u64,
+
+    /// Token reserve after the swap
+    pub token_reserve: u64,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when liquidity is added to or removed from a pool
+#[event]
+pub struct PoolLiquidityEvent {
+    /// Pool PDA
+    pub pool: Pubkey,
+
+    /// Liquidity provider
+    pub user: Pubkey,
+
+    /// True for add_liquidity, false for remove_liquidity
+    pub added: bool,
+
+    /// SOL deposited or withdrawn
+    pub sol_amount: u64,
+
+    /// Tokens deposited or withdrawn
+    pub token_amount: u64,
+
+    /// LP minted or burned
+    pub lp_amount: u64,
+
+    /// LP supply after the change
+    pub lp_supply: u64,
+
+    /// Timestamp
+    pub timestamp: i64,
 }
 
 /// Event emitted when tokens are claimed
@@ -133,3 +422,225 @@ pub struct TokensClaimedEvent {
     /// Timestamp
     pub timestamp: i64,
 }
+
+/// Event emitted when the admin changes the fee schedule for new curves
+#[event]
+pub struct FeeScheduleUpdatedEvent {
+    /// Schedule before the update
+    pub old_schedule: FeeSchedule,
+
+    /// Schedule applied to curves created from now on
+    pub new_schedule: FeeSchedule,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when accrued fees are withdrawn from a curve's FeeVault
+#[event]
+pub struct FeesClaimedEvent {
+    /// Curve ID
+    pub curve: Pubkey,
+
+    /// Ledger the fees were withdrawn from
+    pub recipient: FeeRecipient,
+
+    /// Wallet that received the lamports
+    pub wallet: Pubkey,
+
+    /// Amount claimed (lamports)
+    pub amount: u128,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin updates CurveConfig settings
+#[event]
+pub struct ConfigUpdatedEvent {
+    /// Settings before the update
+    pub old_config: ConfigParams,
+
+    /// Settings after the update
+    pub new_config: ConfigParams,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin proposes (or cancels) an authority handover
+#[event]
+pub struct AuthorityProposedEvent {
+    /// Current authority
+    pub authority: Pubkey,
+
+    /// Proposed authority (None cancels a pending proposal)
+    pub pending_authority: Option<Pubkey>,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when the proposed authority accepts the handover
+#[event]
+pub struct AuthorityTransferredEvent {
+    /// Previous authority
+    pub old_authority: Pubkey,
+
+    /// New authority
+    pub new_authority: Pubkey,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when a sensitive admin action is queued behind the timelock
+#[event]
+pub struct AdminActionQueuedEvent {
+    /// Action id
+    pub id: u64,
+
+    /// Queued change
+    pub action: AdminActionKind,
+
+    /// Authority that queued it
+    pub proposer: Pubkey,
+
+    /// Earliest execution timestamp
+    pub executable_at: i64,
+}
+
+/// Event emitted when a queued admin action is executed
+#[event]
+pub struct AdminActionExecutedEvent {
+    /// Action id
+    pub id: u64,
+
+    /// Applied change
+    pub action: AdminActionKind,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when a queued admin action is cancelled
+#[event]
+pub struct AdminActionCancelledEvent {
+    /// Action id
+    pub id: u64,
+
+    /// Dropped change
+    pub action: AdminActionKind,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when a multisig proposal is created
+#[event]
+pub struct MultisigProposalCreatedEvent {
+    /// Multisig
+    pub multisig: Pubkey,
+
+    /// Proposal account
+    pub proposal: Pubkey,
+
+    /// Proposal id
+    pub id: u64,
+
+    /// Program the proposal invokes
+    pub program_id: Pubkey,
+
+    /// Signer that proposed it (counts as the first approval)
+    pub proposer: Pubkey,
+}
+
+/// Event emitted when a multisig signer approves a proposal
+#[event]
+pub struct MultisigProposalApprovedEvent {
+    /// Proposal account
+    pub proposal: Pubkey,
+
+    /// Approving signer
+    pub signer: Pubkey,
+
+    /// Approvals so far
+    pub approvals: u8,
+}
+
+/// Event emitted when an approved multisig proposal is executed
+#[event]
+pub struct MultisigProposalExecutedEvent {
+    /// Proposal account
+    pub proposal: Pubkey,
+
+    /// Signer that executed it
+    pub executor: Pubkey,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when a wallet is banned
+#[event]
+pub struct AccountBannedEvent {
+    /// Banned wallet
+    pub wallet: Pubkey,
+
+    /// Reason code
+    pub reason: BanReason,
+
+    /// Authority that issued the ban
+    pub authority: Pubkey,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when a ban is lifted
+#[event]
+pub struct AccountUnbannedEvent {
+    /// Unbanned wallet
+    pub wallet: Pubkey,
+
+    /// Authority that lifted the ban
+    pub authority: Pubkey,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when a wallet is reported as a bot
+#[event]
+pub struct BotReportedEvent {
+    /// Reported wallet
+    pub reported: Pubkey,
+
+    /// Reporter
+    pub reporter: Pubkey,
+
+    /// Reason given
+    pub reason: ReportReason,
+
+    /// Distinct reporters so far
+    pub reports: u8,
+
+    /// Whether the wallet is now flagged (trading restricted)
+    pub flagged: bool,
+}
+
+/// Event emitted when the admin resolves a bot report
+#[event]
+pub struct BotReportResolvedEvent {
+    /// Reported wallet
+    pub reported: Pubkey,
+
+    /// True if confirmed as a ban (stakes refunded), false if dismissed (stakes slashed)
+    pub confirmed: bool,
+
+    /// Total reporter stake refunded or slashed (lamports)
+    pub total_stake: u64,
+
+    /// Timestamp
+    pub timestamp: i64,
+}
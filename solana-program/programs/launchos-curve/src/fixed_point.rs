@@ -0,0 +1,137 @@
+//! Fixed-point power module
+//! Computes S^0.6 for the V6 curve without floats (deterministic on-chain).
+//!
+//! S^0.6 = S^(3/5) = 5th root of S^3, so with scale 10^9:
+//!   y = floor(S^0.6 * 10^9) = floor((S^3 * 10^45)^(1/5))
+//! solved with integer Newton iteration:
+//!   y' = (4*y + S^3 * 10^45 / y^4) / 5
+//!
+//! Error bound: result is within 1 ulp (10^-9) of the exact value for
+//! 0 <= S <= MAX_POW_INPUT. Larger inputs return ArithmeticOverflow.
+
+use anchor_lang::prelude::*;
+use crate::errors::CurveError;
+
+/// Fixed-point scale of the result (9 decimals)
+pub const SCALE: u128 = 1_000_000_000;
+
+/// Largest supply accepted by `pow_0_6` (keeps every curve intermediate inside u128)
+pub const MAX_POW_INPUT: u128 = 6_980_000;
+
+/// Calculate S^0.6, returned as S^0.6 * SCALE
+pub fn pow_0_6(x: u128) -> Result<u128> {
+    if x == 0 {
+        return Ok(0);
+    }
+
+    require!(x <= MAX_POW_INPUT, CurveError::ArithmeticOverflow);
+
+    let x_cubed = x
+        .checked_mul(x)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?
+        .checked_mul(x)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?;
+
+    // Initial guess from bit length: x < 2^b => x^0.6 < 2^ceil(3b/5)
+    // Newton on a convex root converges monotonically from above
+    let bits = 128 - x.leading_zeros();
+    let mut y = (1u128 << (3 * bits).div_ceil(5)) * SCALE;
+
+    loop {
+        let next = y
+            .checked_mul(4)
+            .ok_or(error!(CurveError::ArithmeticOverflow))?
+            .checked_add(div_by_fourth_power(x_cubed, y)?)
+            .ok_or(error!(CurveError::ArithmeticOverflow))?
+            / 5;
+
+        if next >= y {
+            return Ok(y);
+        }
+        y = next;
+    }
+}
+
+/// Calculate S^3 * 10^45 / y^4 without a 256-bit intermediate
+/// Divides by y one power at a time, front-loading as much of the 10^45
+/// factor as fits in u128 before each division so truncation stays far
+/// below 1 ulp.
+fn div_by_fourth_power(x_cubed: u128, y: u128) -> Result<u128> {
+    let mut q = x_cubed;
+    let mut remaining_exponent = 45u32;
+
+    for _ in 0..4 {
+        while remaining_exponent > 0 && q <= u128::MAX / 10 {
+            q *= 10;
+            remaining_exponent -= 1;
+        }
+        q /= y;
+    }
+
+    q.checked_mul(10u128.pow(remaining_exponent))
+        .ok_or(error!(CurveError::ArithmeticOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(x: u128) -> f64 {
+        (x as f64).powf(0.6) * SCALE as f64
+    }
+
+    fn assert_close(x: u128) {
+        let result = pow_0_6(x).unwrap() as f64;
+        let expected = reference(x);
+        // 1 ulp of our scale, plus slack for f64 rounding at large magnitudes
+        let tolerance = 1.0 + expected * 1e-15;
+        assert!(
+            (result - expected).abs() <= tolerance,
+            "x = {}: got {} expected {}",
+            x, result, expected
+        );
+    }
+
+    #[test]
+    fn test_pow_0_6_exact_points() {
+        assert_eq!(pow_0_6(0).unwrap(), 0);
+        assert_eq!(pow_0_6(1).unwrap(), SCALE);
+        // 32^0.6 = 8, 1024^0.6 = 64, 100000^0.6 = 1000
+        assert_eq!(pow_0_6(32).unwrap(), 8 * SCALE);
+        assert_eq!(pow_0_6(1024).unwrap(), 64 * SCALE);
+        assert_eq!(pow_0_6(100_000).unwrap(), 1_000 * SCALE);
+    }
+
+    #[test]
+    fn test_pow_0_6_small_supply() {
+        for x in 1..=2_000u128 {
+            assert_close(x);
+        }
+    }
+
+    #[test]
+    fn test_pow_0_6_full_supply_range() {
+        let mut x = 2_000u128;
+        while x <= MAX_POW_INPUT {
+            assert_close(x);
+            assert_close(x + 1);
+            x = x * 11 / 10;
+        }
+        assert_close(MAX_POW_INPUT);
+    }
+
+    #[test]
+    fn test_pow_0_6_monotonic() {
+        let mut previous = 0;
+        for x in 0..5_000u128 {
+            let value = pow_0_6(x).unwrap();
+            assert!(value >= previous);
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn test_pow_0_6_out_of_range() {
+        assert!(pow_0_6(MAX_POW_INPUT + 1).is_err());
+    }
+}
@@ -1,7 +1,14 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use anchor_spl::token::{self, Burn, Mint, MintTo, SetAuthority, Token, TokenAccount, Transfer};
 
+pub mod amm;
 pub mod errors;
 pub mod events;
+pub mod fixed_point;
 pub mod math_v6;
 pub mod state;
 
@@ -14,18 +21,30 @@ use math_v6 as math;
 
 declare_id!("Ej8XrDazXPSRFebCYhycbV1LZGdLHCFddRufRMqZUXQF");
 
-/// Constants for the V6 fee structure
+/// Default V6 fee structure (initial CurveConfig.fee_schedule, editable by admin)
 pub const RESERVE_BPS: u128 = 9400;      // 94% to reserve
 pub const REFERRAL_BPS: u128 = 300;      // 3% referral (flexible routing)
 pub const PROJECT_BPS: u128 = 100;       // 1% project (guaranteed minimum)
 pub const BUYBACK_BPS: u128 = 100;       // 1% buyback/burn
 pub const COMMUNITY_BPS: u128 = 100;     // 1% community rewards
+pub const UNREFERRED_PROJECT_BPS: u128 = 100; // No referrer: 1% of referral to project, 2% to community
 pub const BPS_DENOMINATOR: u128 = 10000;
 
 pub const LOCK_PERIOD_SECONDS: i64 = 604800; // 7 days
 pub const MAX_PURCHASE_DEFAULT: u64 = 100;
 pub const CREATOR_MIN_BUY_PROFILE: u64 = 10;
 pub const TARGET_RESERVE_DEFAULT: u64 = 32_000_000_000; // 32 SOL in lamports
+pub const ADMIN_TIMELOCK_DEFAULT: i64 = 172800; // 48 hours, also the minimum delay
+pub const REPORT_STAKE_LAMPORTS: u64 = 10_000_000; // 0.01 SOL per bot report
+pub const REPORT_FLAG_THRESHOLD: usize = 3; // Distinct reporters to flag a wallet
+pub const REFUND_DELAY_DEFAULT: i64 = 2_592_000; // 30 days after freeze without a launch
+pub const SNAPSHOT_CHALLENGE_PERIOD: i64 = 172800; // 48 hours to challenge a creator-submitted root
+pub const DISPUTE_RESPONSE_PERIOD: i64 = 86400; // 24 hours to answer a membership dispute
+pub const DISPUTE_STAKE_LAMPORTS: u64 = 10_000_000; // 0.01 SOL per membership dispute
+pub const TOKEN_DECIMALS: u8 = 6;
+pub const TOKEN_TOTAL_SUPPLY: u64 = 1_000_000_000_000_000; // 1B tokens (6 decimals), launch_with_mint
+pub const LAUNCH_LIQUIDITY_BPS: u128 = 2000; // 20% of minted supply to liquidity, 80% airdropped
+pub const KEEPER_REWARD_LAMPORTS: u64 = 1_000_000; // 0.001 SOL max per crank_freeze, paid from the keeper pool
 
 #[program]
 pub mod launchos_curve {
@@ -49,6 +68,12 @@ pub mod launchos_curve {
         config.target_reserve_default = TARGET_RESERVE_DEFAULT;
         config.paused = false;
         config.bump = ctx.bumps.config;
+        config.fee_schedule = FeeSchedule::default();
+        config.pause_flags = PauseFlags::default();
+        config.pending_authority = None;
+        config.timelock_delay = ADMIN_TIMELOCK_DEFAULT;
+        config.admin_action_nonce = 0;
+        config.refund_delay = REFUND_DELAY_DEFAULT;
 
         msg!("✅ Curve program initialized");
         msg!("Platform treasury: {}", platform_treasury);
@@ -78,18 +103,22 @@ pub mod launchos_curve {
     ) -> Result<()> {
         // SECURITY: Input validation
         require!(
-            twitter_handle.len() > 0 && twitter_handle.len() <= 32,
+            !twitter_handle.is_empty() && twitter_handle.len() <= 32,
             CurveError::InvalidAmount
         );
 
+        let config = &ctx.accounts.config;
+
+        // SECURITY: Check creation is not paused
+        config.require_creation_enabled()?;
+
         // SECURITY: Check if creator is banned
         require!(
-            !ctx.accounts.ban_list.is_banned(&ctx.accounts.creator.key()),
+            !BanRecord::exists(&ctx.accounts.ban_record),
             CurveError::AccountBanned
         );
 
         let curve = &mut ctx.accounts.curve;
-        let config = &ctx.accounts.config;
         let clock = Clock::get()?;
 
         // Identity
@@ -143,11 +172,20 @@ pub mod launchos_curve {
 
         // Security
         curve.reentrancy_guard = false;
+        curve.admin_paused = false;
 
         // PDA Bumps
         curve.reserve_bump = ctx.bumps.reserve_vault;
         curve.bump = ctx.bumps.curve;
 
+        // Fees (locked in at creation)
+        curve.fee_schedule = config.fee_schedule;
+
+        // Fee vault (trades accrue here, recipients pull with claim_fees)
+        let fee_vault = &mut ctx.accounts.fee_vault;
+        fee_vault.curve = curve.key();
+        fee_vault.bump = ctx.bumps.fee_vault;
+
         msg!("✅ Curve created (PENDING): {}", twitter_handle);
         msg!("Target reserve: {} SOL", curve.target_reserve / 1_000_000_000);
         if let Some(ts) = launch_ts {
@@ -165,6 +203,13 @@ pub mod launchos_curve {
         let curve = &mut ctx.accounts.curve;
         let config = &ctx.accounts.config;
 
+        // SECURITY: Check creation is not paused (globally or for this curve)
+        config.require_creation_enabled()?;
+        curve.require_not_paused()?;
+
+        // SECURITY: Unmigrated curves decode with an all-zero fee schedule (free keys)
+        curve.fee_schedule.validate()?;
+
         // SECURITY: Reentrancy check
         require!(curve.check_reentrancy(), CurveError::ReentrancyDetected);
         curve.set_reentrancy(true);
@@ -193,8 +238,9 @@ pub mod launchos_curve {
         // Calculate price using NEW hybrid exponential formula
         let total_cost = curve.calculate_buy_price(amount)?;
 
-        // Calculate fees using V4 structure
-        let fees = math::calculate_buy_fees(total_cost)?;
+        // V6: No referral code on the creator's own buy
+        let referrer_type = math::ReferrerType::resolve(None, &curve.creator);
+        let fees = math::calculate_v6_buy_fees(total_cost, referrer_type, &curve.fee_schedule)?;
 
         // 1. UPDATE STATE FIRST (CEI pattern)
         curve.supply = curve
@@ -207,9 +253,16 @@ pub mod launchos_curve {
             .checked_add(fees.reserve)
             .ok_or(CurveError::ArithmeticOverflow)?;
 
+        // Project bucket goes to platform_treasury (creator share only on self-referrals)
+        let (creator_fee, treasury_fee) = math::split_project_bucket(
+            total_cost,
+            fees.project,
+            referrer_type,
+            &curve.fee_schedule,
+        )?;
         curve.creator_fees_collected = curve
             .creator_fees_collected
-            .checked_add(fees.instant_fee) // Creator gets instant fee on their own buy
+            .checked_add(creator_fee)
             .ok_or(CurveError::ArithmeticOverflow)?;
 
         curve.rewards_fees_collected = curve
@@ -244,67 +297,25 @@ pub mod launchos_curve {
         holder.acquired_at = clock.unix_timestamp;
         holder.is_creator = true; // 🔒 Subject to lock
         holder.bump = ctx.bumps.key_holder;
-
-        // 2. THEN DO EXTERNAL CALLS (transfers) - V4 Fee Structure
-
-        // Reserve (94%)
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.reserve_vault.to_account_info(),
-                },
-            ),
-            fees.reserve as u64,
-        )?;
-
-        // Instant fee to creator (2%) - creator gets this on their own buy
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.creator.to_account_info(),
-                },
-            ),
-            fees.instant_fee as u64,
-        )?;
-
-        // Buyback/burn (1%)
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.buyback_wallet.to_account_info(),
-                },
-            ),
-            fees.buyback_burn as u64,
-        )?;
-
-        // Community rewards (1%)
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.community_wallet.to_account_info(),
-                },
-            ),
-            fees.community_rewards as u64,
+        holder.referrer = None;
+
+        // Accrue fees to the vault ledgers (same routing as execute_buy)
+        let fees_total = ctx.accounts.fee_vault.accrue(
+            creator_fee,
+            treasury_fee,
+            fees.buyback_burn,
+            fees.community_rewards,
+            fees.referral,
         )?;
 
-        // Platform (2%)
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.platform_treasury.to_account_info(),
-                },
-            ),
-            fees.platform as u64,
+        // 2. THEN DO EXTERNAL CALLS - single transfer, fees moved on to the fee vault
+        deposit_trade(
+            &ctx.accounts.buyer,
+            &ctx.accounts.reserve_vault,
+            &ctx.accounts.fee_vault.to_account_info(),
+            &ctx.accounts.system_program,
+            fees.reserve,
+            fees_total,
         )?;
 
         // 3. CLEAR REENTRANCY GUARD
@@ -321,6 +332,13 @@ pub mod launchos_curve {
     pub fn activate_curve(ctx: Context<ActivateCurve>) -> Result<()> {
         let curve = &mut ctx.accounts.curve;
 
+        // SECURITY: Reject curves not yet resized by migrate_curve (all-zero fee schedule)
+        curve.fee_schedule.validate()?;
+
+        // SECURITY: Check creation is not paused (globally or for this curve)
+        ctx.accounts.config.require_creation_enabled()?;
+        curve.require_not_paused()?;
+
         // SECURITY: Only creator can activate
         require!(
             curve.creator == ctx.accounts.creator.key(),
@@ -350,198 +368,123 @@ pub mod launchos_curve {
         Ok(())
     }
 
-    /// Buy keys (for regular users after curve is ACTIVE)
-    /// V4: Supports referral system with creator fallback
-    pub fn buy_keys(
-        ctx: Context<BuyKeys>,
-        amount: u64,
-        referrer: Option<Pubkey>,
-    ) -> Result<()> {
-        // Manually deserialize config and ban_list to reduce stack usage
-        let config_data = ctx.accounts.config.try_borrow_data()?;
-        let config = CurveConfig::try_deserialize(&mut &config_data[8..])?;
+    /// Abandon a Pending curve (creator only)
+    /// Refunds the reserve, settles accrued fees and closes the curve, holder and vaults,
+    /// so the handle can be used for a new curve
+    /// Remaining accounts: the curve's access list, access entries and referral codes to close
+    /// (each referral code followed by its owner, who gets the rent back)
+    pub fn cancel_curve<'info>(ctx: Context<'_, '_, '_, 'info, CancelCurve<'info>>) -> Result<()> {
+        let curve = &ctx.accounts.curve;
 
-        let ban_list_data = ctx.accounts.ban_list.try_borrow_data()?;
-        let ban_list = BanList::try_deserialize(&mut &ban_list_data[8..])?;
+        // SECURITY: Reject curves not yet resized by migrate_curve (all-zero fee schedule)
+        curve.fee_schedule.validate()?;
 
-        // SECURITY: Reentrancy check
-        require!(ctx.accounts.curve.check_reentrancy(), CurveError::ReentrancyDetected);
-        ctx.accounts.curve.set_reentrancy(true);
+        // SECURITY: Check curve is not paused by the admin
+        curve.require_not_paused()?;
 
-        // SECURITY: Check curve is ACTIVE (not Frozen or Launched)
+        // SECURITY: Only curves that never went public
         require!(
-            ctx.accounts.curve.status == CurveStatus::Active,
-            CurveError::TradingDisabled
+            curve.status == CurveStatus::Pending,
+            CurveError::InvalidStatusTransition
         );
 
-        // SECURITY: Check buyer is not banned
+        // The creator's KeyHolder must be closed along with the curve once they have bought
         require!(
-            !ban_list.is_banned(&ctx.accounts.buyer.key()),
-            CurveError::AccountBanned
+            curve.supply == 0 || ctx.accounts.key_holder.is_some(),
+            CurveError::InvalidAmount
         );
 
-        // SECURITY: Input validation
-        ctx.accounts.curve.validate_amount(amount, config.max_purchase)?;
-
-        // SECURITY: Validate key cap (1% of supply, min 20, max 100)
-        ctx.accounts.curve.validate_key_cap(ctx.accounts.key_holder.amount, amount)?;
-
-        // SECURITY: Validate referrer if provided
-        if let Some(ref_pubkey) = referrer {
-            require!(ref_pubkey != Pubkey::default(), CurveError::InvalidReferrer);
-            require!(ref_pubkey != ctx.accounts.buyer.key(), CurveError::SelfReferral);
+        // Settle the initial buy's fees: other recipients are paid out, the creator's
+        // share comes back when the fee vault closes
+        let fee_vault = &mut ctx.accounts.fee_vault;
+        for (recipient, wallet) in [
+            (FeeRecipient::Treasury, &ctx.accounts.platform_treasury),
+            (FeeRecipient::Buyback, &ctx.accounts.buyback_wallet),
+            (FeeRecipient::Community, &ctx.accounts.community_wallet),
+        ] {
+            let amount = fee_vault.take_owed(recipient);
+            if amount > 0 {
+                fee_vault.sub_lamports(to_lamports(amount)?)?;
+                wallet.add_lamports(to_lamports(amount)?)?;
+            }
         }
 
-        // Calculate price and fees (minimize stack usage)
-        let total_cost = ctx.accounts.curve.calculate_buy_price(amount)?;
-        let fees = math::calculate_buy_fees(total_cost)?;
+        // Reserve and vault rent go back to the creator
+        close_vault(&ctx.accounts.reserve_vault, &ctx.accounts.creator)?;
 
-        // 1. UPDATE STATE FIRST (CEI pattern)
-        ctx.accounts.curve.supply = ctx.accounts.curve
-            .supply
-            .checked_add(amount as u128)
-            .ok_or(CurveError::ArithmeticOverflow)?;
-
-        ctx.accounts.curve.reserve_balance = ctx.accounts.curve
-            .reserve_balance
-            .checked_add(fees.reserve)
-            .ok_or(CurveError::ArithmeticOverflow)?;
-
-        // Track creator fees only if they're the instant recipient
-        if referrer.is_none() {
-            ctx.accounts.curve.creator_fees_collected = ctx.accounts.curve
-                .creator_fees_collected
-                .checked_add(fees.instant_fee)
-                .ok_or(CurveError::ArithmeticOverflow)?;
-        }
-
-        ctx.accounts.curve.rewards_fees_collected = ctx.accounts.curve
-            .rewards_fees_collected
-            .checked_add(fees.buyback_burn)
-            .ok_or(CurveError::ArithmeticOverflow)?
-            .checked_add(fees.community_rewards)
-            .ok_or(CurveError::ArithmeticOverflow)?;
-
-        ctx.accounts.curve.total_buys = ctx.accounts.curve
-            .total_buys
-            .checked_add(1)
-            .ok_or(CurveError::ArithmeticOverflow)?;
-
-        // Update or create key holder
-        if ctx.accounts.key_holder.amount == 0 {
-            let clock = Clock::get()?;
-            ctx.accounts.key_holder.owner = ctx.accounts.buyer.key();
-            ctx.accounts.key_holder.curve = ctx.accounts.curve.key();
-            ctx.accounts.key_holder.amount = amount;
-            ctx.accounts.key_holder.acquired_at = clock.unix_timestamp;
-            ctx.accounts.key_holder.is_creator = false;
-            ctx.accounts.key_holder.bump = ctx.bumps.key_holder;
-
-            // Increment unique holders count
-            ctx.accounts.curve.unique_holders = ctx.accounts.curve
-                .unique_holders
-                .checked_add(1)
-                .ok_or(CurveError::ArithmeticOverflow)?;
-        } else {
-            ctx.accounts.key_holder.amount = ctx.accounts.key_holder
-                .amount
-                .checked_add(amount)
-                .ok_or(CurveError::ArithmeticOverflow)?;
-        }
-
-        // 2. THEN DO EXTERNAL CALLS (transfers) - V4 Fee Structure
-
-        // Reserve (94%)
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.reserve_vault.to_account_info(),
-                },
-            ),
-            fees.reserve as u64,
-        )?;
-
-        // Instant fee (2%) - to referrer OR creator (determined inline)
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: if referrer.is_some() {
-                        ctx.accounts.referrer.as_ref().unwrap().to_account_info()
-                    } else {
-                        ctx.accounts.creator.to_account_info()
-                    },
-                },
-            ),
-            fees.instant_fee as u64,
+        // A new curve on the handle gets the same PDA, so it must not inherit these
+        close_curve_accounts(
+            &curve.key(),
+            ctx.remaining_accounts,
+            &ctx.accounts.creator.to_account_info(),
         )?;
 
-        // Buyback/burn (1%)
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.buyback_wallet.to_account_info(),
-                },
-            ),
-            fees.buyback_burn as u64,
-        )?;
+        emit!(CurveCancelledEvent {
+            curve: curve.key(),
+            creator: curve.creator,
+            twitter_handle: curve.twitter_handle.clone(),
+            refunded: curve.reserve_balance,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Community rewards (1%)
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.community_wallet.to_account_info(),
-                },
-            ),
-            fees.community_rewards as u64,
-        )?;
+        msg!("Curve CANCELLED: {} | Refunded: {} lamports", curve.twitter_handle, curve.reserve_balance);
+        Ok(())
+    }
 
-        // Platform (2%)
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.platform_treasury.to_account_info(),
-                },
-            ),
-            fees.platform as u64,
-        )?;
+    /// Buy keys (for regular users after curve is ACTIVE)
+    /// V6: Fee routing by referrer type (user / project self-referral / none)
+    /// Referrer comes from the holder's sticky binding, or the `referral_code` account on first buy
+    /// SECURITY: Reverts if cost exceeds `max_total_cost` or `deadline` has passed
+    pub fn buy_keys(
+        ctx: Context<BuyKeys>,
+        amount: u64,
+        max_total_cost: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        // SECURITY: Reject stale transactions
+        check_deadline(deadline)?;
 
-        // 3. CLEAR REENTRANCY GUARD
-        ctx.accounts.curve.set_reentrancy(false);
+        execute_buy(ctx, BuyOrder::ExactKeys { amount, max_total_cost })
+    }
 
-        // Emit event
-        emit!(KeysPurchasedEvent {
-            curve: ctx.accounts.curve.key(),
-            buyer: ctx.accounts.buyer.key(),
-            amount,
-            cost: total_cost,
-            referrer,
-            instant_fee_paid: fees.instant_fee,
-        });
+    /// Buy keys by budget: spend at most `max_lamports`, receive the most keys it affords
+    /// Fees are carved out of the total cost, so the budget covers reserve + every fee bucket
+    /// SECURITY: Same status, ban, max-purchase and key-cap checks as `buy_keys`
+    pub fn buy_keys_exact_in(
+        ctx: Context<BuyKeys>,
+        max_lamports: u64,
+        min_keys: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        // SECURITY: Reject stale transactions
+        check_deadline(deadline)?;
 
-        msg!("Keys purchased: {} for {} lamports", amount, total_cost);
-        Ok(())
+        execute_buy(ctx, BuyOrder::ExactIn { max_lamports, min_keys })
     }
 
     /// Sell keys
-    /// V4: Unified 6% fee model (94% to seller, 6% split into fees)
+    /// V6: Unified 6% fee model (94% to seller, 6% routed by referrer type)
+    /// Referrer comes from the holder's sticky binding
+    /// SECURITY: Reverts if payout is below `min_payout` or `deadline` has passed
     pub fn sell_keys(
         ctx: Context<SellKeys>,
         amount: u64,
-        referrer: Option<Pubkey>, // Optional referrer (same as buy)
+        min_payout: u64,
+        deadline: Option<i64>,
     ) -> Result<()> {
         let curve = &mut ctx.accounts.curve;
         let holder = &mut ctx.accounts.key_holder;
 
+        // SECURITY: Reject stale transactions
+        check_deadline(deadline)?;
+
+        // SECURITY: Check trading is not paused (globally or for this curve)
+        ctx.accounts.config.require_trading_enabled()?;
+        curve.require_not_paused()?;
+
+        // SECURITY: Unmigrated curves decode with an all-zero fee schedule (free keys)
+        curve.fee_schedule.validate()?;
+
         // SECURITY: Reentrancy check
         require!(curve.check_reentrancy(), CurveError::ReentrancyDetected);
         curve.set_reentrancy(true);
@@ -552,9 +495,10 @@ pub mod launchos_curve {
             CurveError::TradingDisabled
         );
 
-        // SECURITY: Check seller is not banned
+        // SECURITY: Check seller is not banned (a flag pending review only blocks buys,
+        // so a wrongly reported wallet can always exit)
         require!(
-            !ctx.accounts.ban_list.is_banned(&ctx.accounts.seller.key()),
+            !BanRecord::exists(&ctx.accounts.ban_record),
             CurveError::AccountBanned
         );
 
@@ -571,20 +515,31 @@ pub mod launchos_curve {
             );
         }
 
-        // SECURITY: Validate referrer if provided
-        if let Some(ref_pubkey) = referrer {
-            require!(ref_pubkey != Pubkey::default(), CurveError::InvalidReferrer);
-            require!(ref_pubkey != ctx.accounts.seller.key(), CurveError::SelfReferral);
-        }
+        // Resolve referrer from the holder's binding (sells never create one)
+        let referrer = resolve_referrer(
+            holder,
+            &mut ctx.accounts.referral_code,
+            &ctx.accounts.seller.key(),
+            false,
+        )?;
+
+        // V6: Derive fee routing on-chain (user referral, project self-referral, or none)
+        let referrer_type = math::ReferrerType::resolve(referrer, &curve.creator);
 
         // Calculate gross return (before fees)
         let gross_return = math::calculate_buy_cost(curve.supply - amount as u128, amount)?;
 
-        // Calculate V4 fee distribution (6% total)
-        let sell_fees = math::calculate_sell_fees(gross_return)?;
+        // Calculate V6 fee distribution (6% total)
+        let sell_fees = math::calculate_v6_sell_fees(gross_return, referrer_type, &curve.fee_schedule)?;
+
+        // SECURITY: Slippage protection (price may have moved since signing)
+        require!(
+            sell_fees.to_seller >= min_payout as u128,
+            CurveError::PayoutBelowMinimum
+        );
 
         // Total to deduct from reserve
-        let total_from_reserve = gross_return; // Seller gets net, fees stay in reserve
+        let total_from_reserve = gross_return; // Seller gets net, fees move to the fee vault
 
         // SECURITY: Check reserve has enough balance
         require!(
@@ -592,32 +547,32 @@ pub mod launchos_curve {
             CurveError::InsufficientReserve
         );
 
-        // Determine instant fee recipient (referrer OR creator fallback)
-        let (instant_recipient, instant_recipient_key) = if let Some(ref_pubkey) = referrer {
-            (ctx.accounts.referrer.as_ref().unwrap().to_account_info(), ref_pubkey)
-        } else {
-            (ctx.accounts.creator.to_account_info(), curve.creator)
-        };
-
         // 1. UPDATE STATE FIRST (CEI pattern)
         curve.supply = curve
             .supply
             .checked_sub(amount as u128)
             .ok_or(CurveError::ArithmeticOverflow)?;
 
-        // Reserve reduces by gross amount (fees are distributed separately)
+        // Reserve reduces by gross amount (fees accrue in the fee vault)
         curve.reserve_balance = curve
             .reserve_balance
             .checked_sub(gross_return)
             .ok_or(CurveError::ArithmeticOverflow)?;
 
-        // Track creator fees if they're the instant recipient
-        if referrer.is_none() {
-            curve.creator_fees_collected = curve
-                .creator_fees_collected
-                .checked_add(sell_fees.instant_fee)
-                .ok_or(CurveError::ArithmeticOverflow)?;
-        }
+        // Project bucket goes to platform_treasury (creator share only on self-referrals)
+        let (creator_fee, treasury_fee) = math::split_project_bucket(
+            gross_return,
+            sell_fees.project,
+            referrer_type,
+            &curve.fee_schedule,
+        )?;
+        curve.creator_fees_collected = curve
+            .creator_fees_collected
+            .checked_add(creator_fee)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+
+        // Referral earnings accrue to the code's ledger
+        credit_referral(&mut ctx.accounts.referral_code, sell_fees.referral)?;
 
         curve.rewards_fees_collected = curve
             .rewards_fees_collected
@@ -636,79 +591,31 @@ pub mod launchos_curve {
             .checked_sub(amount)
             .ok_or(CurveError::ArithmeticOverflow)?;
 
-        // 2. THEN DO EXTERNAL CALLS (transfers from reserve)
-        let curve_key = curve.key();
-        let seeds = &[
-            b"reserve",
-            curve_key.as_ref(),
-            &[curve.reserve_bump],
-        ];
-        let signer = &[&seeds[..]];
-
-        // Pay seller (94% of gross)
-        anchor_lang::system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.reserve_vault.to_account_info(),
-                    to: ctx.accounts.seller.to_account_info(),
-                },
-                signer,
-            ),
-            sell_fees.to_seller as u64,
-        )?;
-
-        // Instant fee (2% of gross) - to referrer OR creator
-        anchor_lang::system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.reserve_vault.to_account_info(),
-                    to: instant_recipient,
-                },
-                signer,
-            ),
-            sell_fees.instant_fee as u64,
-        )?;
-
-        // Buyback/burn (1% of gross)
-        anchor_lang::system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.reserve_vault.to_account_info(),
-                    to: ctx.accounts.buyback_wallet.to_account_info(),
-                },
-                signer,
-            ),
-            sell_fees.buyback_burn as u64,
-        )?;
+        // Fully exited holders no longer count (buy_keys counts them again on re-entry)
+        if holder.amount == 0 {
+            curve.unique_holders = curve
+                .unique_holders
+                .checked_sub(1)
+                .ok_or(CurveError::ArithmeticOverflow)?;
+        }
 
-        // Community rewards (1% of gross)
-        anchor_lang::system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.reserve_vault.to_account_info(),
-                    to: ctx.accounts.community_wallet.to_account_info(),
-                },
-                signer,
-            ),
-            sell_fees.community_rewards as u64,
+        // Accrue fees to the vault ledgers
+        let fees_total = ctx.accounts.fee_vault.accrue(
+            creator_fee,
+            treasury_fee,
+            sell_fees.buyback_burn,
+            sell_fees.community_rewards,
+            sell_fees.referral,
         )?;
 
-        // Platform (2% of gross)
-        anchor_lang::system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.reserve_vault.to_account_info(),
-                    to: ctx.accounts.platform_treasury.to_account_info(),
-                },
-                signer,
-            ),
-            sell_fees.platform as u64,
-        )?;
+        // 2. THEN MOVE LAMPORTS (reserve vault and fee vault are program-owned, no CPI)
+        let payout = sell_fees
+            .to_seller
+            .checked_add(fees_total)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+        ctx.accounts.reserve_vault.sub_lamports(to_lamports(payout)?)?;
+        ctx.accounts.seller.add_lamports(to_lamports(sell_fees.to_seller)?)?;
+        ctx.accounts.fee_vault.add_lamports(to_lamports(fees_total)?)?;
 
         // 3. CLEAR REENTRANCY GUARD
         curve.set_reentrancy(false);
@@ -720,489 +627,4287 @@ pub mod launchos_curve {
             amount,
             payout: sell_fees.to_seller,
             gross_return,
+            referrer,
+            referrer_type,
+            fees: sell_fees,
         });
 
         msg!("✅ Keys sold: {} | Seller received: {} lamports", amount, sell_fees.to_seller);
-        msg!("Instant fee recipient: {} earned {} lamports",
-            instant_recipient_key,
-            sell_fees.instant_fee
+        msg!("Fee routing: {:?} | Referral: {} | Project: {} lamports",
+            referrer_type,
+            sell_fees.referral,
+            sell_fees.project
         );
         Ok(())
     }
 
-    /// Report a bot account (admin will review and ban)
-    pub fn report_bot(ctx: Context<ReportBot>, reported_account: Pubkey) -> Result<()> {
-        msg!("Bot report submitted for: {}", reported_account);
-        msg!("Reporter: {}", ctx.accounts.reporter.key());
-        // In production, emit an event that admin dashboard picks up
+    /// Register a referral code on a curve (owner receives referral fees)
+    /// Buyers bind to a code on their first buy; the binding is permanent
+    pub fn register_referrer(ctx: Context<RegisterReferrer>, code: String) -> Result<()> {
+        require!(Referrer::is_valid_code(&code), CurveError::InvalidReferralCode);
+
+        let referrer = &mut ctx.accounts.referrer;
+        referrer.curve = ctx.accounts.curve.key();
+        referrer.owner = ctx.accounts.owner.key();
+        referrer.code = code.clone();
+        referrer.referred_holders = 0;
+        referrer.total_earned = 0;
+        referrer.unclaimed_fees = 0;
+        referrer.created_at = Clock::get()?.unix_timestamp;
+        referrer.bump = ctx.bumps.referrer;
+
+        emit!(ReferrerRegisteredEvent {
+            curve: referrer.curve,
+            referrer: referrer.key(),
+            owner: referrer.owner,
+            code: code.clone(),
+        });
+
+        msg!("Referral code registered: {}", code);
         Ok(())
     }
 
-    /// Ban an account (admin only)
-    pub fn ban_account(ctx: Context<BanAccount>, account_to_ban: Pubkey) -> Result<()> {
-        let ban_list = &mut ctx.accounts.ban_list;
+    /// Read-only quote: gross price, V6 fee split, remaining wallet cap and post-trade spot price
+    /// Result is written with set_return_data (Anchor return value); simulate to read it
+    pub fn quote(
+        ctx: Context<Quote>,
+        side: TradeSide,
+        amount: u64,
+        referrer_type: math::ReferrerType,
+    ) -> Result<TradeQuote> {
+        let curve = &ctx.accounts.curve;
 
-        // SECURITY: Only admin can ban
-        require!(
-            ban_list.authority == ctx.accounts.authority.key(),
-            CurveError::Unauthorized
-        );
+        require!(amount > 0, CurveError::InvalidAmount);
+        curve.fee_schedule.validate()?;
+
+        let (gross, fees, supply_after) = match side {
+            TradeSide::Buy => {
+                let cost = curve.calculate_buy_price(amount)?;
+                let fees = math::calculate_v6_buy_fees(cost, referrer_type, &curve.fee_schedule)?;
+                let supply_after = curve
+                    .supply
+                    .checked_add(amount as u128)
+                    .ok_or(CurveError::ArithmeticOverflow)?;
+                (cost, QuoteFees::Buy(fees), supply_after)
+            }
+            TradeSide::Sell => {
+                let supply_after = curve
+                    .supply
+                    .checked_sub(amount as u128)
+                    .ok_or(CurveError::InsufficientReserve)?;
+                let gross_return = math::calculate_buy_cost(supply_after, amount)?;
+                let fees = math::calculate_v6_sell_fees(gross_return, referrer_type, &curve.fee_schedule)?;
+                (gross_return, QuoteFees::Sell(fees), supply_after)
+            }
+        };
 
-        // Add to ban list if not already banned
-        if !ban_list.is_banned(&account_to_ban) {
-            ban_list.banned_accounts.push(account_to_ban);
-            msg!("Account banned: {}", account_to_ban);
-        }
+        let held = ctx.accounts.key_holder.as_ref().map_or(0, |holder| holder.amount);
 
-        Ok(())
+        Ok(TradeQuote {
+            side,
+            amount,
+            gross,
+            fees,
+            remaining_key_cap: curve.get_max_keys_per_wallet().saturating_sub(held),
+            spot_price_after: math::calculate_price_at_supply(supply_after)?,
+        })
     }
 
-    /// Emergency pause (admin only)
-    pub fn pause(ctx: Context<AdminAction>) -> Result<()> {
-        let config = &mut ctx.accounts.config;
-
-        require!(
-            config.authority == ctx.accounts.authority.key(),
-            CurveError::Unauthorized
+    /// Withdraw a fee ledger from the curve's FeeVault to its fixed recipient
+    /// Permissionless: lamports can only go to the address recorded on the curve
+    pub fn claim_fees(ctx: Context<ClaimFees>, recipient: FeeRecipient) -> Result<()> {
+        let curve = &ctx.accounts.curve;
+
+        // SECURITY: Check claims are not paused
+        ctx.accounts.config.require_claims_enabled()?;
+
+        let expected_wallet = match recipient {
+            FeeRecipient::Creator => curve.creator,
+            FeeRecipient::Treasury => curve.platform_treasury,
+            FeeRecipient::Buyback => curve.buyback_wallet,
+            FeeRecipient::Community => curve.community_wallet,
+            // Referral balances are per code, see claim_referral_fees
+            FeeRecipient::Referrer => return err!(CurveError::InvalidFeeRecipient),
+        };
+        require_keys_eq!(
+            ctx.accounts.recipient_wallet.key(),
+            expected_wallet,
+            CurveError::InvalidFeeRecipient
         );
 
-        config.paused = true;
-        msg!("🛑 Curve program PAUSED");
+        // 1. UPDATE STATE FIRST (CEI pattern)
+        let amount = ctx.accounts.fee_vault.take_owed(recipient);
+        require!(amount > 0, CurveError::NothingToClaim);
+
+        // 2. THEN MOVE LAMPORTS (fee vault is program-owned)
+        ctx.accounts.fee_vault.sub_lamports(to_lamports(amount)?)?;
+        ctx.accounts.recipient_wallet.add_lamports(to_lamports(amount)?)?;
+
+        emit!(FeesClaimedEvent {
+            curve: curve.key(),
+            recipient,
+            wallet: expected_wallet,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Fees claimed: {:?} | {} lamports", recipient, amount);
         Ok(())
     }
 
-    /// Unpause (admin only)
-    pub fn unpause(ctx: Context<AdminAction>) -> Result<()> {
-        let config = &mut ctx.accounts.config;
+    /// Withdraw a referral code's unclaimed fees to its owner
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        // SECURITY: Check claims are not paused
+        ctx.accounts.config.require_claims_enabled()?;
 
-        require!(
-            config.authority == ctx.accounts.authority.key(),
-            CurveError::Unauthorized
-        );
+        let code = &mut ctx.accounts.referral_code;
 
-        config.paused = false;
-        msg!("✅ Curve program UNPAUSED");
+        // 1. UPDATE STATE FIRST (CEI pattern)
+        let amount = std::mem::take(&mut code.unclaimed_fees);
+        require!(amount > 0, CurveError::NothingToClaim);
+
+        let fee_vault = &mut ctx.accounts.fee_vault;
+        fee_vault.referral_owed = fee_vault
+            .referral_owed
+            .checked_sub(amount)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+
+        // 2. THEN MOVE LAMPORTS (fee vault is program-owned)
+        fee_vault.sub_lamports(to_lamports(amount)?)?;
+        ctx.accounts.owner.add_lamports(to_lamports(amount)?)?;
+
+        emit!(FeesClaimedEvent {
+            curve: ctx.accounts.curve.key(),
+            recipient: FeeRecipient::Referrer,
+            wallet: ctx.accounts.owner.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Referral fees claimed: {} lamports", amount);
         Ok(())
     }
 
-    // ============================================================================
-    // V6 FREEZE SYSTEM - MANUAL ONLY
-    // ============================================================================
+    /// Report a bot account with a refundable stake (admin will review and ban)
+    /// REPORT_FLAG_THRESHOLD distinct reporters flag the wallet, blocking its buys (sells stay open)
+    pub fn report_bot(
+        ctx: Context<ReportBot>,
+        reported_account: Pubkey,
+        reason: ReportReason,
+    ) -> Result<()> {
+        let reporter = ctx.accounts.reporter.key();
+        require!(reporter != reported_account, CurveError::SelfReport);
 
-    /// V6: Manually freeze the curve (creator only)
-    /// No auto-freeze in V6 - requires explicit creator action at 32+ SOL reserve
-    pub fn freeze_curve(ctx: Context<FreezeCurve>) -> Result<()> {
-        let curve = &mut ctx.accounts.curve;
         let clock = Clock::get()?;
+        let bot_report = &mut ctx.accounts.bot_report;
+
+        // First report opens the account
+        if bot_report.reporters.is_empty() {
+            bot_report.reported = reported_account;
+            bot_report.status = ReportStatus::Open;
+            bot_report.flagged_at = None;
+            bot_report.total_stake = 0;
+            bot_report.bump = ctx.bumps.bot_report;
+        }
 
-        // SECURITY: Only creator can freeze
         require!(
-            curve.creator == ctx.accounts.creator.key(),
-            CurveError::Unauthorized
+            !bot_report.reporters.iter().any(|entry| entry.reporter == reporter),
+            CurveError::DuplicateReport
         );
-
-        // SECURITY: Check minimum reserve threshold (32 SOL)
         require!(
-            curve.reserve_balance >= TARGET_RESERVE_DEFAULT as u128,
-            CurveError::ReserveThresholdNotMet
+            bot_report.reporters.len() < BotReport::MAX_REPORTERS,
+            CurveError::ReportFull
         );
 
-        // SECURITY: Must be in Active status
-        require!(
-            curve.status == CurveStatus::Active,
-            CurveError::InvalidStatusTransition
-        );
+        bot_report.reporters.push(ReportEntry {
+            reporter,
+            reason,
+            reported_at: clock.unix_timestamp,
+        });
+        bot_report.total_stake = bot_report
+            .total_stake
+            .checked_add(REPORT_STAKE_LAMPORTS)
+            .ok_or(CurveError::ArithmeticOverflow)?;
 
-        // Execute freeze
-        curve.execute_freeze("manual", clock.unix_timestamp)?;
+        if bot_report.status == ReportStatus::Open
+            && bot_report.reporters.len() >= REPORT_FLAG_THRESHOLD
+        {
+            bot_report.status = ReportStatus::Flagged;
+            bot_report.flagged_at = Some(clock.unix_timestamp);
+            msg!("🚩 Account flagged pending review: {}", reported_account);
+        }
 
-        // Emit event
-        emit!(CurveFrozenEvent {
-            curve_id: curve.key(),
-            trigger: "manual".to_string(),
-            supply: curve.supply_at_freeze,
-            reserve: curve.reserve_at_freeze,
-            timestamp: clock.unix_timestamp,
+        let reports = bot_report.reporters.len() as u8;
+        let flagged = bot_report.status == ReportStatus::Flagged;
+
+        // Stake is held by the report account until the admin resolves it
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.reporter.to_account_info(),
+                    to: ctx.accounts.bot_report.to_account_info(),
+                },
+            ),
+            REPORT_STAKE_LAMPORTS,
+        )?;
+
+        emit!(BotReportedEvent {
+            reported: reported_account,
+            reporter,
+            reason,
+            reports,
+            flagged,
         });
 
-        msg!("❄️ Curve FROZEN manually by creator");
-        msg!("Supply at freeze: {}", curve.supply_at_freeze);
-        msg!("Reserve at freeze: {} SOL", curve.reserve_at_freeze / 1_000_000_000);
+        msg!("Bot report submitted for: {} ({:?})", reported_account, reason);
         Ok(())
     }
 
-    // ============================================================================
-    // V4 SNAPSHOT SYSTEM
-    // ============================================================================
-
-    /// Create snapshot with Merkle root (after freeze, before launch)
-    pub fn create_snapshot(
-        ctx: Context<CreateSnapshot>,
-        merkle_root: [u8; 32],
-        total_holders: u32,
-    ) -> Result<()> {
-        let curve = &mut ctx.accounts.curve;
-        let snapshot = &mut ctx.accounts.snapshot;
-        let clock = Clock::get()?;
-
-        // SECURITY: Only creator can create snapshot
+    /// Confirm a bot report (ban authority only)
+    /// Bans the wallet with the first reporter's reason and refunds every stake;
+    /// reporters are passed as remaining accounts in report order
+    pub fn confirm_bot_report(ctx: Context<ConfirmBotReport>) -> Result<()> {
         require!(
-            curve.creator == ctx.accounts.creator.key(),
+            ctx.accounts.ban_list.authority == ctx.accounts.authority.key(),
             CurveError::Unauthorized
         );
 
-        // SECURITY: Curve must be frozen
+        let bot_report = &ctx.accounts.bot_report;
+        let clock = Clock::get()?;
+        let reason = bot_report.reporters[0].reason.ban_reason();
+
+        // Already banned (e.g. via ban_wallet): keep the original record, still resolve the report
+        let ban_record = &mut ctx.accounts.ban_record;
+        let newly_banned = ban_record.wallet == Pubkey::default();
+        if newly_banned {
+            ban_record.wallet = bot_report.reported;
+            ban_record.reason = reason;
+            ban_record.authority = ctx.accounts.authority.key();
+            ban_record.banned_at = clock.unix_timestamp;
+            ban_record.bump = ctx.bumps.ban_record;
+        }
+
+        // Refund stakes (report account is program-owned, no CPI)
         require!(
-            curve.status == CurveStatus::Frozen,
-            CurveError::CurveNotFrozen
+            ctx.remaining_accounts.len() == bot_report.reporters.len(),
+            CurveError::InvalidReporterAccounts
         );
+        for (entry, reporter) in bot_report.reporters.iter().zip(ctx.remaining_accounts) {
+            require_keys_eq!(reporter.key(), entry.reporter, CurveError::InvalidReporterAccounts);
+            ctx.accounts.bot_report.sub_lamports(REPORT_STAKE_LAMPORTS)?;
+            reporter.add_lamports(REPORT_STAKE_LAMPORTS)?;
+        }
 
-        // SECURITY: Snapshot can only be created once
+        if newly_banned {
+            emit!(AccountBannedEvent {
+                wallet: ban_record.wallet,
+                reason,
+                authority: ban_record.authority,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        emit!(BotReportResolvedEvent {
+            reported: ban_record.wallet,
+            confirmed: true,
+            total_stake: ctx.accounts.bot_report.total_stake,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Bot report confirmed, account banned: {}", ban_record.wallet);
+        Ok(())
+    }
+
+    /// Dismiss a bot report (ban authority only)
+    /// Slashes every reporter stake to the community wallet and lifts the flag
+    pub fn dismiss_bot_report(ctx: Context<DismissBotReport>) -> Result<()> {
         require!(
-            curve.snapshot_root.is_none(),
-            CurveError::SnapshotAlreadyExists
+            ctx.accounts.ban_list.authority == ctx.accounts.authority.key(),
+            CurveError::Unauthorized
         );
 
-        // Initialize snapshot account
-        snapshot.curve = curve.key();
-        snapshot.merkle_root = merkle_root;
-        snapshot.total_supply = curve.supply_at_freeze;
-        snapshot.total_holders = total_holders;
-        snapshot.total_token_pool = 0; // Set after launch when tokens are bought
-        snapshot.created_at = clock.unix_timestamp;
-        snapshot.bump = ctx.bumps.snapshot;
+        let total_stake = ctx.accounts.bot_report.total_stake;
+        let reported = ctx.accounts.bot_report.reported;
 
-        // Store merkle root in curve
-        curve.snapshot_root = Some(merkle_root);
+        // Slash stakes (report account is program-owned, no CPI)
+        ctx.accounts.bot_report.sub_lamports(total_stake)?;
+        ctx.accounts.community_wallet.add_lamports(total_stake)?;
 
-        // Emit event
-        emit!(SnapshotCreatedEvent {
-            curve_id: curve.key(),
-            snapshot: snapshot.key(),
-            merkle_root,
-            total_supply: curve.supply_at_freeze,
-            total_holders,
-            timestamp: clock.unix_timestamp,
+        emit!(BotReportResolvedEvent {
+            reported,
+            confirmed: false,
+            total_stake,
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("📸 Snapshot created");
-        msg!("Merkle root: {:?}", merkle_root);
-        msg!("Total holders: {}", total_holders);
+        msg!("Bot report dismissed: {} | {} lamports slashed", reported, total_stake);
         Ok(())
     }
 
     // ============================================================================
-    // V4 LAUNCH SYSTEM
+    // CURVE ACCESS LISTS (creator managed)
     // ============================================================================
 
-    /// Launch token (split reserve, emit event for off-chain service)
-    pub fn launch(
-        ctx: Context<LaunchToken>,
-        initial_buy_percentage: u8, // 20-30%
+    /// Create the curve's access list (creator only)
+    pub fn create_access_list(ctx: Context<CreateAccessList>, mode: AccessMode) -> Result<()> {
+        let access_list = &mut ctx.accounts.access_list;
+        access_list.curve = ctx.accounts.curve.key();
+        access_list.mode = mode;
+        access_list.bump = ctx.bumps.access_list;
+
+        msg!("Access list created for {}: {:?}", ctx.accounts.curve.twitter_handle, mode);
+        Ok(())
+    }
+
+    /// Switch between deny-list and allowlist-only gating (creator only)
+    pub fn set_access_mode(ctx: Context<UpdateAccessList>, mode: AccessMode) -> Result<()> {
+        ctx.accounts.access_list.mode = mode;
+
+        msg!("Access mode for {}: {:?}", ctx.accounts.curve.twitter_handle, mode);
+        Ok(())
+    }
+
+    /// Allow or deny a wallet on the curve (creator only)
+    pub fn set_access_entry(
+        ctx: Context<SetAccessEntry>,
+        wallet: Pubkey,
+        kind: AccessKind,
     ) -> Result<()> {
-        let curve = &mut ctx.accounts.curve;
-        let clock = Clock::get()?;
+        let entry = &mut ctx.accounts.access_entry;
+        entry.curve = ctx.accounts.curve.key();
+        entry.wallet = wallet;
+        entry.kind = kind;
+        entry.bump = ctx.bumps.access_entry;
 
-        // SECURITY: Only creator can launch
+        msg!("Access entry for {}: {:?}", wallet, kind);
+        Ok(())
+    }
+
+    /// Remove a wallet's allow/deny entry (creator only)
+    pub fn remove_access_entry(ctx: Context<RemoveAccessEntry>) -> Result<()> {
+        msg!("Access entry removed for {}", ctx.accounts.access_entry.wallet);
+        Ok(())
+    }
+
+    /// Ban an account (admin only)
+    /// Creates the wallet's BanRecord PDA; trades check for its existence
+    pub fn ban_account(
+        ctx: Context<BanAccount>,
+        account_to_ban: Pubkey,
+        reason: BanReason,
+    ) -> Result<()> {
+        // SECURITY: Only admin can ban
         require!(
-            curve.creator == ctx.accounts.creator.key(),
+            ctx.accounts.ban_list.authority == ctx.accounts.authority.key(),
             CurveError::Unauthorized
         );
 
-        // SECURITY: Must be frozen and have snapshot
-        require!(curve.can_launch(), CurveError::CurveNotFrozen);
+        let clock = Clock::get()?;
+        let ban_record = &mut ctx.accounts.ban_record;
+        ban_record.wallet = account_to_ban;
+        ban_record.reason = reason;
+        ban_record.authority = ctx.accounts.authority.key();
+        ban_record.banned_at = clock.unix_timestamp;
+        ban_record.bump = ctx.bumps.ban_record;
+
+        emit!(AccountBannedEvent {
+            wallet: account_to_ban,
+            reason,
+            authority: ban_record.authority,
+            timestamp: clock.unix_timestamp,
+        });
 
-        // SECURITY: Prevent double launch
-        require!(!curve.is_launching, CurveError::AlreadyLaunched);
-        curve.is_launching = true;
+        msg!("Account banned: {} ({:?})", account_to_ban, reason);
+        Ok(())
+    }
 
-        // Validate percentage (20-30%)
+    /// Lift a ban (admin only), closing the BanRecord
+    pub fn unban_account(ctx: Context<UnbanAccount>) -> Result<()> {
         require!(
-            initial_buy_percentage >= 20 && initial_buy_percentage <= 30,
-            CurveError::InvalidAmount
+            ctx.accounts.ban_list.authority == ctx.accounts.authority.key(),
+            CurveError::Unauthorized
         );
 
-        let total_reserve = curve.reserve_at_freeze;
+        let wallet = ctx.accounts.ban_record.wallet;
 
-        // Calculate splits
-        let initial_buy_sol = total_reserve
-            .checked_mul(initial_buy_percentage as u128)
-            .ok_or(CurveError::ArithmeticOverflow)?
-            .checked_div(100)
-            .ok_or(CurveError::ArithmeticOverflow)?;
+        emit!(AccountUnbannedEvent {
+            wallet,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        let marketing_sol = total_reserve
-            .checked_mul(50)
-            .ok_or(CurveError::ArithmeticOverflow)?
-            .checked_div(100)
-            .ok_or(CurveError::ArithmeticOverflow)?;
+        msg!("Account unbanned: {}", wallet);
+        Ok(())
+    }
 
-        let utility_sol = total_reserve
-            .checked_sub(initial_buy_sol)
-            .ok_or(CurveError::ArithmeticOverflow)?
-            .checked_sub(marketing_sol)
-            .ok_or(CurveError::ArithmeticOverflow)?;
+    /// Move one wallet from the legacy BanList Vec to its BanRecord (permissionless)
+    /// Run for every legacy entry after upgrading: trades only check BanRecords
+    pub fn migrate_ban(ctx: Context<MigrateBan>, wallet: Pubkey) -> Result<()> {
+        let ban_list = &mut ctx.accounts.ban_list;
+
+        let index = ban_list
+            .banned_accounts
+            .iter()
+            .position(|banned| *banned == wallet)
+            .ok_or(CurveError::NotBanned)?;
+        ban_list.banned_accounts.swap_remove(index);
+
+        let clock = Clock::get()?;
+        let ban_record = &mut ctx.accounts.ban_record;
+        ban_record.wallet = wallet;
+        ban_record.reason = BanReason::Legacy;
+        ban_record.authority = ban_list.authority;
+        ban_record.banned_at = clock.unix_timestamp;
+        ban_record.bump = ctx.bumps.ban_record;
+
+        msg!("Legacy ban migrated: {} ({} left)", wallet, ban_list.banned_accounts.len());
+        Ok(())
+    }
+
+    /// Hand the ban list to a new authority, e.g. a multisig signer (admin only)
+    pub fn set_ban_list_authority(
+        ctx: Context<SetBanListAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let ban_list = &mut ctx.accounts.ban_list;
 
-        // SECURITY: Check reserve has enough funds
         require!(
-            curve.reserve_balance >= total_reserve,
-            CurveError::InsufficientReserve
+            ban_list.authority == ctx.accounts.authority.key(),
+            CurveError::Unauthorized
         );
 
-        // 1. UPDATE STATE FIRST
-        curve.status = CurveStatus::Launched;
-        curve.launched_at = Some(clock.unix_timestamp);
-        curve.initial_buy_amount = initial_buy_sol;
-        curve.lp_vault = Some(ctx.accounts.lp_vault.key());
-        curve.marketing_wallet = Some(ctx.accounts.marketing_wallet.key());
-        curve.utility_wallet = Some(ctx.accounts.utility_wallet.key());
-
-        // Reduce reserve balance
-        curve.reserve_balance = curve
-            .reserve_balance
-            .checked_sub(total_reserve)
-            .ok_or(CurveError::ArithmeticOverflow)?;
+        ban_list.authority = new_authority;
+        msg!("Ban list authority set to: {}", new_authority);
+        Ok(())
+    }
 
-        // 2. TRANSFER FUNDS FROM RESERVE
-        let curve_key = curve.key();
-        let seeds = &[
-            b"reserve",
-            curve_key.as_ref(),
-            &[curve.reserve_bump],
-        ];
-        let signer = &[&seeds[..]];
+    /// Emergency pause (admin only)
+    pub fn pause(ctx: Context<AdminAction>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
 
-        // Transfer to LP vault (for Pump.fun initial buy)
-        anchor_lang::system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.reserve_vault.to_account_info(),
-                    to: ctx.accounts.lp_vault.to_account_info(),
-                },
-                signer,
-            ),
-            initial_buy_sol as u64,
-        )?;
+        require!(
+            config.authority == ctx.accounts.authority.key(),
+            CurveError::Unauthorized
+        );
 
-        // Transfer to marketing wallet
-        anchor_lang::system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.reserve_vault.to_account_info(),
-                    to: ctx.accounts.marketing_wallet.to_account_info(),
-                },
-                signer,
-            ),
-            marketing_sol as u64,
-        )?;
+        config.paused = true;
+        msg!("🛑 Curve program PAUSED");
+        Ok(())
+    }
 
-        // Transfer to utility wallet
-        anchor_lang::system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.reserve_vault.to_account_info(),
-                    to: ctx.accounts.utility_wallet.to_account_info(),
-                },
-                signer,
-            ),
-            utility_sol as u64,
-        )?;
+    /// Unpause (admin only)
+    pub fn unpause(ctx: Context<AdminAction>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
 
-        // 3. EMIT EVENT FOR OFF-CHAIN SERVICE
-        emit!(CurveLaunchedEvent {
-            curve_id: curve.key(),
-            token_mint: None, // Set by off-chain service after Pump.fun creation
-            snapshot_root: curve.snapshot_root.unwrap(),
-            supply_at_launch: curve.supply_at_freeze,
-            initial_buy_sol,
-            marketing_sol,
-            utility_sol,
-            lp_vault: ctx.accounts.lp_vault.key(),
-            marketing_wallet: ctx.accounts.marketing_wallet.key(),
-            utility_wallet: ctx.accounts.utility_wallet.key(),
-            timestamp: clock.unix_timestamp,
-        });
+        require!(
+            config.authority == ctx.accounts.authority.key(),
+            CurveError::Unauthorized
+        );
 
-        msg!("🚀 LAUNCHED!");
-        msg!("Initial buy: {} SOL ({} %)", initial_buy_sol / 1_000_000_000, initial_buy_percentage);
-        msg!("Marketing: {} SOL", marketing_sol / 1_000_000_000);
-        msg!("Utility: {} SOL", utility_sol / 1_000_000_000);
-        msg!("Off-chain service will now:");
-        msg!("1. Create token on Pump.fun");
-        msg!("2. Buy tokens with LP vault SOL");
-        msg!("3. Store tokens in airdrop vault");
-        msg!("4. Key holders can then claim via Merkle proof");
+        config.paused = false;
+        msg!("✅ Curve program UNPAUSED");
         Ok(())
     }
 
-    // ============================================================================
-    // V4 CLAIM SYSTEM
-    // ============================================================================
-
-    /// Claim tokens using Merkle proof
-    pub fn claim_tokens(
-        ctx: Context<ClaimTokens>,
-        proof: Vec<[u8; 32]>,
-    ) -> Result<()> {
-        let snapshot = &ctx.accounts.snapshot;
-        let holder = &ctx.accounts.key_holder;
-        let claim_record = &mut ctx.accounts.claim_record;
-        let clock = Clock::get()?;
+    /// Accept the admin authority (step 2 of 2, signed by the proposed authority)
+    pub fn accept_authority(ctx: Context<AdminAction>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
 
-        // SECURITY: Prevent double claiming
         require!(
-            claim_record.amount_claimed == 0,
-            CurveError::AlreadyClaimed
+            config.pending_authority == Some(ctx.accounts.authority.key()),
+            CurveError::NotPendingAuthority
         );
 
-        // Verify Merkle proof
-        let leaf = anchor_lang::solana_program::keccak::hashv(&[
-            holder.owner.as_ref(),
-            &holder.amount.to_le_bytes(),
-        ]);
+        let old_authority = config.authority;
+        config.authority = ctx.accounts.authority.key();
+        config.pending_authority = None;
 
-        let is_valid = verify_merkle_proof(&proof, &snapshot.merkle_root, &leaf.0);
-        require!(is_valid, CurveError::InvalidMerkleProof);
+        emit!(AuthorityTransferredEvent {
+            old_authority,
+            new_authority: config.authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Calculate token allocation
-        // (holder's keys / total keys) * total token pool
-        let token_amount = (holder.amount as u128)
+        msg!("Authority transferred: {} -> {}", old_authority, config.authority);
+        Ok(())
+    }
+
+    /// Update the curve limits in CurveConfig (admin only, instant)
+    /// Wallets, fees, refund delay and authority changes go through the admin timelock
+    pub fn update_config(ctx: Context<AdminAction>, update: ConfigUpdate) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            config.authority == ctx.accounts.authority.key(),
+            CurveError::Unauthorized
+        );
+
+        let old_config = config.params();
+        config.apply_update(&update)?;
+
+        emit!(ConfigUpdatedEvent {
+            old_config,
+            new_config: config.params(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Config updated");
+        Ok(())
+    }
+
+    /// Set granular pause flags (admin only)
+    /// Applies on top of the global switch: `unpause` does not clear these
+    pub fn set_pause_flags(ctx: Context<AdminAction>, pause_flags: PauseFlags) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            config.authority == ctx.accounts.authority.key(),
+            CurveError::Unauthorized
+        );
+
+        config.pause_flags = pause_flags;
+        msg!("Pause flags updated: {:?}", pause_flags);
+        Ok(())
+    }
+
+    /// Pause or resume a single curve (admin only)
+    /// Blocks trading, activation and launch for that curve; claims stay open
+    pub fn set_curve_paused(ctx: Context<AdminCurveAction>, paused: bool) -> Result<()> {
+        require!(
+            ctx.accounts.config.authority == ctx.accounts.authority.key(),
+            CurveError::Unauthorized
+        );
+
+        let curve = &mut ctx.accounts.curve;
+        curve.fee_schedule.validate()?;
+        curve.admin_paused = paused;
+
+        msg!("Curve {} {}", curve.twitter_handle, if paused { "PAUSED" } else { "UNPAUSED" });
+        Ok(())
+    }
+
+    // ============================================================================
+    // TIMELOCKED ADMIN ACTIONS
+    // ============================================================================
+
+    /// Queue a sensitive config change (admin only)
+    /// Executable after `config.timelock_delay`; pause controls stay instant
+    pub fn queue_admin_action(
+        ctx: Context<QueueAdminAction>,
+        action: AdminActionKind,
+    ) -> Result<()> {
+        queue_action(ctx, action)
+    }
+
+    /// Queue a fee schedule change for newly created curves (admin only, timelocked)
+    /// Existing curves keep the schedule recorded at their creation
+    pub fn update_fee_schedule(
+        ctx: Context<QueueAdminAction>,
+        fee_schedule: FeeSchedule,
+    ) -> Result<()> {
+        queue_action(ctx, AdminActionKind::UpdateFeeSchedule(fee_schedule))
+    }
+
+    /// Queue a new admin authority proposal (step 1 of 2, admin only, timelocked)
+    /// Passing None cancels a pending proposal; the new authority then calls accept_authority
+    pub fn propose_authority(
+        ctx: Context<QueueAdminAction>,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        queue_action(ctx, AdminActionKind::ProposeAuthority(new_authority))
+    }
+
+    /// Execute a queued admin action once its delay has passed (admin only)
+    pub fn execute_admin_action(ctx: Context<ResolveAdminAction>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let pending = &ctx.accounts.pending_action;
+
+        require!(
+            config.authority == ctx.accounts.authority.key(),
+            CurveError::Unauthorized
+        );
+
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= pending.executable_at,
+            CurveError::TimelockNotElapsed
+        );
+
+        apply_admin_action(config, &pending.action, Some(clock.unix_timestamp))?;
+
+        emit!(AdminActionExecutedEvent {
+            id: pending.id,
+            action: pending.action,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Admin action #{} executed", pending.id);
+        Ok(())
+    }
+
+    /// Cancel a queued admin action (admin only)
+    pub fn cancel_admin_action(ctx: Context<ResolveAdminAction>) -> Result<()> {
+        require!(
+            ctx.accounts.config.authority == ctx.accounts.authority.key(),
+            CurveError::Unauthorized
+        );
+
+        let pending = &ctx.accounts.pending_action;
+
+        emit!(AdminActionCancelledEvent {
+            id: pending.id,
+            action: pending.action,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Admin action #{} cancelled", pending.id);
+        Ok(())
+    }
+
+    // ============================================================================
+    // MULTISIG AUTHORITY
+    // ============================================================================
+
+    /// Create an M-of-N multisig
+    /// Its signer PDA can then be set as CurveConfig, BanList or EscrowAccount authority
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        multisig_id: u64,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        Multisig::validate_signers(&signers, threshold)?;
+
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.signers = signers;
+        multisig.threshold = threshold;
+        multisig.nonce = 0;
+        multisig.owner_set_seqno = 0;
+        multisig.signer_bump = ctx.bumps.multisig_signer;
+        multisig.bump = ctx.bumps.multisig;
+
+        msg!("Multisig #{} created: {}-of-{}", multisig_id, threshold, multisig.signers.len());
+        msg!("Multisig signer: {}", ctx.accounts.multisig_signer.key());
+        Ok(())
+    }
+
+    /// Replace the signer set and threshold (signed by the multisig itself via a proposal)
+    /// Open proposals made against the old set can no longer execute
+    pub fn set_multisig_signers(
+        ctx: Context<SetMultisigSigners>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        Multisig::validate_signers(&signers, threshold)?;
+
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.signers = signers;
+        multisig.threshold = threshold;
+        multisig.owner_set_seqno = multisig
+            .owner_set_seqno
+            .checked_add(1)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+
+        msg!("Multisig signers updated: {}-of-{}", threshold, multisig.signers.len());
+        Ok(())
+    }
+
+    /// Propose an instruction for the multisig signer to execute (signers only)
+    /// The proposer's approval is recorded immediately
+    pub fn propose_multisig_action(
+        ctx: Context<ProposeMultisigAction>,
+        program_id: Pubkey,
+        accounts: Vec<ProposalAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+        let proposer_index = multisig.signer_index(&ctx.accounts.proposer.key())?;
+
+        // SECURITY: The multisig signer PDA is the only signer a proposal can supply
+        let multisig_signer = ctx.accounts.multisig_signer.key();
+        require!(
+            accounts.len() <= MultisigProposal::MAX_ACCOUNTS
+                && data.len() <= MultisigProposal::MAX_DATA_LEN
+                && accounts
+                    .iter()
+                    .all(|account| !account.is_signer || account.pubkey == multisig_signer),
+            CurveError::InvalidProposal
+        );
+
+        let mut approvals = vec![false; multisig.signers.len()];
+        approvals[proposer_index] = true;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.multisig = multisig.key();
+        proposal.id = multisig.nonce;
+        proposal.program_id = program_id;
+        proposal.accounts = accounts;
+        proposal.data = data;
+        proposal.approvals = approvals;
+        proposal.owner_set_seqno = multisig.owner_set_seqno;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.created_at = Clock::get()?.unix_timestamp;
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+
+        multisig.nonce = multisig
+            .nonce
+            .checked_add(1)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+
+        emit!(MultisigProposalCreatedEvent {
+            multisig: proposal.multisig,
+            proposal: proposal.key(),
+            id: proposal.id,
+            program_id,
+            proposer: proposal.proposer,
+        });
+
+        msg!("Multisig proposal #{} created", proposal.id);
+        Ok(())
+    }
+
+    /// Approve a multisig proposal (signers only)
+    pub fn approve_multisig_action(ctx: Context<ApproveMultisigAction>) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let proposal = &mut ctx.accounts.proposal;
+
+        require!(!proposal.executed, CurveError::ProposalAlreadyExecuted);
+        require!(
+            proposal.owner_set_seqno == multisig.owner_set_seqno,
+            CurveError::ProposalStale
+        );
+
+        let signer_index = multisig.signer_index(&ctx.accounts.signer.key())?;
+        proposal.approvals[signer_index] = true;
+
+        emit!(MultisigProposalApprovedEvent {
+            proposal: proposal.key(),
+            signer: ctx.accounts.signer.key(),
+            approvals: proposal.approval_count() as u8,
+        });
+
+        msg!("Multisig proposal #{} approved ({}/{})",
+            proposal.id,
+            proposal.approval_count(),
+            multisig.threshold
+        );
+        Ok(())
+    }
+
+    /// Execute a proposal that reached the threshold (signers only)
+    /// Target program and all proposal accounts are passed as remaining accounts
+    pub fn execute_multisig_action(ctx: Context<ExecuteMultisigAction>) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let proposal = &mut ctx.accounts.proposal;
+
+        multisig.signer_index(&ctx.accounts.executor.key())?;
+
+        require!(!proposal.executed, CurveError::ProposalAlreadyExecuted);
+        require!(
+            proposal.owner_set_seqno == multisig.owner_set_seqno,
+            CurveError::ProposalStale
+        );
+        require!(
+            proposal.approval_count() >= multisig.threshold as usize,
+            CurveError::MultisigThresholdNotMet
+        );
+
+        // 1. UPDATE STATE FIRST (CEI pattern)
+        proposal.executed = true;
+
+        // 2. THEN INVOKE, signed by the multisig signer PDA
+        let instruction = Instruction {
+            program_id: proposal.program_id,
+            accounts: proposal
+                .accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.pubkey,
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: proposal.data.clone(),
+        };
+
+        let multisig_key = multisig.key();
+        let seeds = &[
+            b"multisig_signer",
+            multisig_key.as_ref(),
+            &[multisig.signer_bump],
+        ];
+        invoke_signed(&instruction, ctx.remaining_accounts, &[&seeds[..]])?;
+
+        emit!(MultisigProposalExecutedEvent {
+            proposal: proposal.key(),
+            executor: ctx.accounts.executor.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Multisig proposal #{} executed", proposal.id);
+        Ok(())
+    }
+
+    // ============================================================================
+    // ACCOUNT MIGRATIONS (pre-upgrade layouts)
+    // ============================================================================
+
+    /// Grow the pre-upgrade config to the current layout with `initialize` defaults (permissionless)
+    /// Every instruction that reads the config fails until this has run once
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let info = ctx.accounts.config.to_account_info();
+        require!(
+            info.data_len() != CurveConfig::LEN,
+            CurveError::AccountAlreadyMigrated
+        );
+
+        let config = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == <CurveConfig as anchor_lang::Discriminator>::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            LegacyCurveConfig::deserialize(&mut &data[8..])?.migrate()
+        };
+
+        resize_account(&info, &ctx.accounts.payer, &ctx.accounts.system_program, CurveConfig::LEN)?;
+        config.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        msg!("Config migrated (authority {})", config.authority);
+        Ok(())
+    }
+
+    /// Rewrite a pre-upgrade curve in the current layout and open its FeeVault (permissionless)
+    /// Required for every pre-upgrade curve, frozen or not: converts the legacy string freeze
+    /// trigger, reallocs to BondingCurve::LEN and writes FeeSchedule::default() and
+    /// admin_paused = false. Until then trades reject the curve (its padding decodes as an
+    /// all-zero fee schedule)
+    pub fn migrate_curve(ctx: Context<MigrateCurve>) -> Result<()> {
+        let info = ctx.accounts.curve.to_account_info();
+        require!(
+            info.data_len() != BondingCurve::LEN,
+            CurveError::AccountAlreadyMigrated
+        );
+
+        let curve = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == <BondingCurve as anchor_lang::Discriminator>::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            LegacyBondingCurve::deserialize(&mut &data[8..])?.migrate()?
+        };
+
+        resize_account(&info, &ctx.accounts.payer, &ctx.accounts.system_program, BondingCurve::LEN)?;
+        curve.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        let fee_vault = &mut ctx.accounts.fee_vault;
+        fee_vault.curve = info.key();
+        fee_vault.bump = ctx.bumps.fee_vault;
+
+        msg!("Curve migrated: {}", curve.twitter_handle);
+        Ok(())
+    }
+
+    /// Grow a pre-upgrade KeyHolder to the current layout, unbound (permissionless)
+    /// Legacy holders fail to deserialize until migrated; `payer` tops up the rent
+    pub fn migrate_key_holder(ctx: Context<MigrateAccount>) -> Result<()> {
+        let info = ctx.accounts.account.to_account_info();
+        require!(
+            info.data_len() != KeyHolder::LEN,
+            CurveError::AccountAlreadyMigrated
+        );
+
+        let holder = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == <KeyHolder as anchor_lang::Discriminator>::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            LegacyKeyHolder::deserialize(&mut &data[8..])?.migrate()
+        };
+
+        resize_account(&info, &ctx.accounts.payer, &ctx.accounts.system_program, KeyHolder::LEN)?;
+        holder.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        msg!("KeyHolder migrated: {} on {}", holder.owner, holder.curve);
+        Ok(())
+    }
+
+    // ============================================================================
+    // V6 FREEZE SYSTEM
+    // ============================================================================
+
+    /// V6: Manually freeze the curve (creator only)
+    /// Requires 32+ SOL reserve; buys and crank_freeze also freeze automatically
+    pub fn freeze_curve(ctx: Context<FreezeCurve>) -> Result<()> {
+        let curve = &mut ctx.accounts.curve;
+        let clock = Clock::get()?;
+
+        // SECURITY: Reject curves not yet resized by migrate_curve (all-zero fee schedule)
+        curve.fee_schedule.validate()?;
+
+        // SECURITY: Only creator can freeze
+        require!(
+            curve.creator == ctx.accounts.creator.key(),
+            CurveError::Unauthorized
+        );
+
+        // SECURITY: Check the curve's reserve threshold (target_reserve set at creation)
+        require!(
+            curve.is_reserve_threshold_met(),
+            CurveError::ReserveThresholdNotMet
+        );
+
+        // SECURITY: Must be in Active status
+        require!(
+            curve.status == CurveStatus::Active,
+            CurveError::InvalidStatusTransition
+        );
+
+        // Execute freeze
+        freeze_and_emit(curve, FreezeTrigger::Manual, clock.unix_timestamp)?;
+
+        msg!("❄️ Curve FROZEN manually by creator");
+        msg!("Supply at freeze: {}", curve.supply_at_freeze);
+        msg!("Reserve at freeze: {} SOL", curve.reserve_at_freeze / 1_000_000_000);
+        Ok(())
+    }
+
+    /// Permissionless: freeze a curve whose reserve reached target_reserve or whose launch_ts passed
+    /// The keeper gets up to KEEPER_REWARD_LAMPORTS from the KeeperPool (nothing if it is absent or
+    /// empty); trade fees never fund the reward
+    pub fn crank_freeze(ctx: Context<CrankFreeze>) -> Result<()> {
+        let curve = &mut ctx.accounts.curve;
+        let clock = Clock::get()?;
+
+        // SECURITY: Reject curves not yet resized by migrate_curve (all-zero fee schedule)
+        curve.fee_schedule.validate()?;
+
+        // SECURITY: Check curve is not paused by the admin
+        curve.require_not_paused()?;
+
+        // SECURITY: Must be in Active status
+        require!(
+            curve.status == CurveStatus::Active,
+            CurveError::InvalidStatusTransition
+        );
+
+        // Reserve threshold takes precedence, then the creator's launch time
+        let trigger = if curve.is_reserve_threshold_met() {
+            FreezeTrigger::ReserveThreshold
+        } else {
+            require!(curve.launch_ts.is_some(), CurveError::LaunchTimeNotSet);
+            require!(
+                curve.is_time_threshold_met(clock.unix_timestamp),
+                CurveError::LaunchTimeNotReached
+            );
+            FreezeTrigger::TimeBased
+        };
+
+        // 1. UPDATE STATE FIRST (CEI pattern)
+        freeze_and_emit(curve, trigger, clock.unix_timestamp)?;
+
+        let mut reward = 0;
+        if let Some(keeper_pool) = ctx.accounts.keeper_pool.as_mut() {
+            // SECURITY: Never dip into the pool's rent-exempt minimum
+            let rent = Rent::get()?.minimum_balance(KeeperPool::LEN);
+            reward = keeper_pool
+                .get_lamports()
+                .saturating_sub(rent)
+                .min(KEEPER_REWARD_LAMPORTS);
+            keeper_pool.total_paid = keeper_pool
+                .total_paid
+                .checked_add(reward)
+                .ok_or(CurveError::ArithmeticOverflow)?;
+
+            // 2. THEN MOVE LAMPORTS (keeper pool is program-owned)
+            if reward > 0 {
+                keeper_pool.sub_lamports(reward)?;
+                ctx.accounts.keeper.add_lamports(reward)?;
+            }
+        }
+
+        msg!("❄️ Curve FROZEN by crank | Trigger: {:?} | Keeper reward: {} lamports", trigger, reward);
+        Ok(())
+    }
+
+    /// Deposit lamports into the KeeperPool that pays crank_freeze rewards (permissionless)
+    pub fn fund_keeper_pool(ctx: Context<FundKeeperPool>, amount: u64) -> Result<()> {
+        require!(amount > 0, CurveError::InvalidAmount);
+
+        let keeper_pool = &mut ctx.accounts.keeper_pool;
+        keeper_pool.total_funded = keeper_pool
+            .total_funded
+            .checked_add(amount)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+        keeper_pool.bump = ctx.bumps.keeper_pool;
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.keeper_pool.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Keeper pool funded: {} lamports", amount);
+        Ok(())
+    }
+
+    /// Freeze an active curve immediately, regardless of thresholds (admin only)
+    pub fn emergency_freeze(ctx: Context<AdminCurveAction>) -> Result<()> {
+        require!(
+            ctx.accounts.config.authority == ctx.accounts.authority.key(),
+            CurveError::Unauthorized
+        );
+
+        let curve = &mut ctx.accounts.curve;
+        curve.fee_schedule.validate()?;
+        require!(
+            curve.status == CurveStatus::Active,
+            CurveError::InvalidStatusTransition
+        );
+
+        freeze_and_emit(curve, FreezeTrigger::AdminEmergency, Clock::get()?.unix_timestamp)?;
+
+        msg!("🚨 Curve {} FROZEN by admin", curve.twitter_handle);
+        Ok(())
+    }
+
+    // ============================================================================
+    // V4 SNAPSHOT SYSTEM
+    // ============================================================================
+
+    /// Create snapshot with Merkle root (after freeze, before launch)
+    pub fn create_snapshot(
+        ctx: Context<CreateSnapshot>,
+        merkle_root: [u8; 32],
+        total_holders: u32,
+    ) -> Result<()> {
+        let curve = &mut ctx.accounts.curve;
+        let snapshot = &mut ctx.accounts.snapshot;
+        let clock = Clock::get()?;
+
+        // SECURITY: Reject curves not yet resized by migrate_curve (all-zero fee schedule)
+        curve.fee_schedule.validate()?;
+
+        // SECURITY: Only creator can create snapshot
+        require!(
+            curve.creator == ctx.accounts.creator.key(),
+            CurveError::Unauthorized
+        );
+
+        // SECURITY: Curve must be frozen
+        require!(
+            curve.status == CurveStatus::Frozen,
+            CurveError::CurveNotFrozen
+        );
+
+        // SECURITY: Snapshot can only be created once
+        require!(
+            curve.snapshot_root.is_none(),
+            CurveError::SnapshotAlreadyExists
+        );
+
+        // SECURITY: Once begin_snapshot has started, only its on-chain root can be used
+        require!(
+            ctx.accounts.builder.data_is_empty(),
+            CurveError::SnapshotInProgress
+        );
+
+        // Initialize snapshot account
+        snapshot.curve = curve.key();
+        snapshot.merkle_root = merkle_root;
+        snapshot.total_supply = curve.supply_at_freeze;
+        snapshot.total_holders = total_holders;
+        snapshot.total_token_pool = 0; // Set after launch when tokens are bought
+        snapshot.created_at = clock.unix_timestamp;
+        snapshot.bump = ctx.bumps.snapshot;
+
+        // Creator-submitted roots are open to challenges before launch
+        snapshot.status = SnapshotStatus::Challengeable;
+        snapshot.challenge_ends_at = clock
+            .unix_timestamp
+            .checked_add(SNAPSHOT_CHALLENGE_PERIOD)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+        snapshot.open_disputes = 0;
+
+        // Store merkle root in curve
+        curve.snapshot_root = Some(merkle_root);
+
+        // Emit event
+        emit!(SnapshotCreatedEvent {
+            curve_id: curve.key(),
+            snapshot: snapshot.key(),
+            merkle_root,
+            total_supply: curve.supply_at_freeze,
+            total_holders,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("📸 Snapshot created");
+        msg!("Merkle root: {:?}", merkle_root);
+        msg!("Total holders: {}", total_holders);
+        Ok(())
+    }
+
+    /// Start an on-chain snapshot of a frozen curve (permissionless)
+    /// Alternative to create_snapshot that does not trust a creator-supplied root
+    pub fn begin_snapshot(ctx: Context<BeginSnapshot>) -> Result<()> {
+        let curve = &ctx.accounts.curve;
+
+        // SECURITY: Curve must be frozen and not yet snapshotted
+        require!(
+            curve.status == CurveStatus::Frozen,
+            CurveError::CurveNotFrozen
+        );
+        require!(
+            curve.snapshot_root.is_none(),
+            CurveError::SnapshotAlreadyExists
+        );
+
+        let builder = &mut ctx.accounts.builder;
+        builder.curve = curve.key();
+        builder.payer = ctx.accounts.payer.key();
+        builder.holder_count = 0;
+        builder.registered_supply = 0;
+        builder.filled_subtrees = [[0u8; 32]; SnapshotBuilder::DEPTH];
+        builder.root = [0u8; 32];
+        builder.bump = ctx.bumps.builder;
+
+        msg!("📸 Snapshot started for {} ({} holders)", curve.twitter_handle, curve.unique_holders);
+        Ok(())
+    }
+
+    /// Append one KeyHolder to the curve's snapshot tree (permissionless)
+    /// Batch several per transaction; each holder can only be registered once
+    pub fn register_snapshot_holder(ctx: Context<RegisterSnapshotHolder>) -> Result<()> {
+        let curve = &ctx.accounts.curve;
+        let holder = &ctx.accounts.key_holder;
+
+        require!(
+            curve.status == CurveStatus::Frozen,
+            CurveError::CurveNotFrozen
+        );
+        require!(
+            curve.snapshot_root.is_none(),
+            CurveError::SnapshotAlreadyExists
+        );
+
+        // Holders that sold out are not part of the snapshot
+        require!(holder.amount > 0, CurveError::InvalidAmount);
+
+        let builder = &mut ctx.accounts.builder;
+        let leaf_index = builder.insert(&holder.owner, holder.amount)?;
+
+        // Registration marker, later used by claim_tokens
+        let claim_record = &mut ctx.accounts.claim_record;
+        claim_record.snapshot = ctx.accounts.snapshot.key();
+        claim_record.holder = holder.owner;
+        claim_record.amount_claimed = 0;
+        claim_record.claimed_at = 0;
+        claim_record.bump = ctx.bumps.claim_record;
+
+        emit!(SnapshotHolderRegisteredEvent {
+            curve: curve.key(),
+            holder: holder.owner,
+            amount: holder.amount,
+            leaf_index,
+            root: builder.root,
+        });
+        Ok(())
+    }
+
+    /// Publish the on-chain snapshot root once every holder is registered (permissionless)
+    /// SECURITY: Registered keys must equal supply_at_freeze
+    pub fn finalize_snapshot(ctx: Context<FinalizeSnapshot>) -> Result<()> {
+        let curve = &mut ctx.accounts.curve;
+        let builder = &ctx.accounts.builder;
+        let clock = Clock::get()?;
+
+        // SECURITY: Reject curves not yet resized by migrate_curve (all-zero fee schedule)
+        curve.fee_schedule.validate()?;
+
+        require!(
+            curve.status == CurveStatus::Frozen,
+            CurveError::CurveNotFrozen
+        );
+        require!(
+            curve.snapshot_root.is_none(),
+            CurveError::SnapshotAlreadyExists
+        );
+        // Each holder registers once with amount > 0, so matching the supply means every
+        // holder is in the tree (unique_holders is not used: legacy curves overcount it)
+        require!(
+            builder.registered_supply == curve.supply_at_freeze,
+            CurveError::SnapshotIncomplete
+        );
+
+        let snapshot = &mut ctx.accounts.snapshot;
+        snapshot.curve = curve.key();
+        snapshot.merkle_root = builder.root;
+        snapshot.total_supply = curve.supply_at_freeze;
+        snapshot.total_holders = builder.holder_count;
+        snapshot.total_token_pool = 0; // Set after launch when tokens are bought
+        snapshot.created_at = clock.unix_timestamp;
+        snapshot.bump = ctx.bumps.snapshot;
+        snapshot.status = SnapshotStatus::Verified;
+        snapshot.challenge_ends_at = clock.unix_timestamp;
+        snapshot.open_disputes = 0;
+
+        curve.snapshot_root = Some(builder.root);
+
+        emit!(SnapshotCreatedEvent {
+            curve_id: curve.key(),
+            snapshot: snapshot.key(),
+            merkle_root: builder.root,
+            total_supply: curve.supply_at_freeze,
+            total_holders: builder.holder_count,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("📸 On-chain snapshot finalized | Holders: {}", builder.holder_count);
+        Ok(())
+    }
+
+    /// Close a snapshot builder left behind once the curve has a root (permissionless)
+    /// e.g. a creator root that went through first, or a curve that moved to refunds;
+    /// rent goes back to whoever paid for the builder
+    pub fn close_snapshot_builder(ctx: Context<CloseSnapshotBuilder>) -> Result<()> {
+        let curve = &ctx.accounts.curve;
+
+        require!(
+            curve.snapshot_root.is_some() || curve.status == CurveStatus::Refunding,
+            CurveError::SnapshotNotCreated
+        );
+
+        msg!("Snapshot builder closed for {}", curve.twitter_handle);
+        Ok(())
+    }
+
+    /// Invalidate a creator-submitted root that contains a wrong leaf (permissionless)
+    /// Proves leaf (holder, amount) is in the root while the holder's KeyHolder says otherwise
+    /// (no KeyHolder counts as 0 keys); the challenger receives the snapshot's rent
+    pub fn challenge_snapshot_amount(
+        ctx: Context<ChallengeSnapshotAmount>,
+        holder: Pubkey,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.snapshot.is_challengeable(clock.unix_timestamp),
+            CurveError::SnapshotNotChallengeable
+        );
+
+        let leaf = snapshot_leaf(&holder, amount);
+        require!(
+            verify_merkle_proof(&proof, &ctx.accounts.snapshot.merkle_root, &leaf),
+            CurveError::InvalidMerkleProof
+        );
+
+        // Keys are fixed once frozen, so the KeyHolder is the ground truth
+        let key_holder = &ctx.accounts.key_holder;
+        let actual = if key_holder.owner == &crate::ID && !key_holder.data_is_empty() {
+            KeyHolder::try_deserialize(&mut &key_holder.try_borrow_data()?[..])?.amount
+        } else {
+            0
+        };
+        require!(amount != actual, CurveError::NoAmountMismatch);
+
+        invalidate_snapshot(
+            &mut ctx.accounts.curve,
+            &ctx.accounts.snapshot.to_account_info(),
+            ctx.accounts.snapshot.merkle_root,
+            &ctx.accounts.challenger,
+            SnapshotChallenge::AmountMismatch,
+            holder,
+            clock.unix_timestamp,
+        )
+    }
+
+    /// Dispute a holder's absence from a creator-submitted root (stakes DISPUTE_STAKE_LAMPORTS)
+    /// Blocks launch until answered with a membership proof or upheld
+    pub fn dispute_snapshot_membership(ctx: Context<DisputeSnapshotMembership>) -> Result<()> {
+        let clock = Clock::get()?;
+        let snapshot = &mut ctx.accounts.snapshot;
+        let holder = &ctx.accounts.key_holder;
+
+        require!(
+            snapshot.is_challengeable(clock.unix_timestamp),
+            CurveError::SnapshotNotChallengeable
+        );
+        require!(holder.amount > 0, CurveError::InvalidAmount);
+
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.curve = ctx.accounts.curve.key();
+        dispute.holder = holder.owner;
+        dispute.challenger = ctx.accounts.challenger.key();
+        dispute.merkle_root = snapshot.merkle_root;
+        dispute.snapshot_created_at = snapshot.created_at;
+        dispute.opened_at = clock.unix_timestamp;
+        dispute.bump = ctx.bumps.dispute;
+
+        snapshot.open_disputes = snapshot
+            .open_disputes
+            .checked_add(1)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+
+        // Stake is held by the dispute account until it is answered or upheld
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.challenger.to_account_info(),
+                    to: ctx.accounts.dispute.to_account_info(),
+                },
+            ),
+            DISPUTE_STAKE_LAMPORTS,
+        )?;
+
+        emit!(SnapshotDisputeOpenedEvent {
+            curve: ctx.accounts.curve.key(),
+            holder: holder.owner,
+            challenger: ctx.accounts.challenger.key(),
+            respond_by: clock.unix_timestamp.saturating_add(DISPUTE_RESPONSE_PERIOD),
+        });
+
+        msg!("Snapshot membership disputed for: {}", holder.owner);
+        Ok(())
+    }
+
+    /// Answer a membership dispute with the holder's Merkle proof (permissionless)
+    /// The stake goes to the creator; the dispute's rent returns to the challenger
+    pub fn answer_snapshot_dispute(
+        ctx: Context<AnswerSnapshotDispute>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let snapshot = &mut ctx.accounts.snapshot;
+        let dispute = &ctx.accounts.dispute;
+        let holder = &ctx.accounts.key_holder;
+
+        require!(dispute.targets(snapshot), CurveError::DisputeStale);
+
+        let leaf = snapshot_leaf(&holder.owner, holder.amount);
+        require!(
+            verify_merkle_proof(&proof, &snapshot.merkle_root, &leaf),
+            CurveError::InvalidMerkleProof
+        );
+
+        snapshot.open_disputes = snapshot
+            .open_disputes
+            .checked_sub(1)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+
+        // Dispute account is program-owned, no CPI
+        ctx.accounts.dispute.sub_lamports(DISPUTE_STAKE_LAMPORTS)?;
+        ctx.accounts.creator.add_lamports(DISPUTE_STAKE_LAMPORTS)?;
+
+        emit!(SnapshotDisputeResolvedEvent {
+            curve: ctx.accounts.curve.key(),
+            holder: holder.owner,
+            upheld: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Snapshot dispute answered for: {}", holder.owner);
+        Ok(())
+    }
+
+    /// Close an unanswered or stale membership dispute, refunding the stake (permissionless)
+    /// Unanswered after DISPUTE_RESPONSE_PERIOD, it invalidates the disputed root
+    pub fn uphold_snapshot_dispute(ctx: Context<UpholdSnapshotDispute>) -> Result<()> {
+        let clock = Clock::get()?;
+        let dispute = &ctx.accounts.dispute;
+
+        // The snapshot PDA may have been closed already by another invalidation
+        let snapshot_info = ctx.accounts.snapshot.to_account_info();
+        if snapshot_info.owner == &crate::ID && !snapshot_info.data_is_empty() {
+            let snapshot = Snapshot::try_deserialize(&mut &snapshot_info.try_borrow_data()?[..])?;
+
+            if dispute.targets(&snapshot) {
+                require!(
+                    clock.unix_timestamp
+                        >= dispute.opened_at.saturating_add(DISPUTE_RESPONSE_PERIOD),
+                    CurveError::DisputeResponsePending
+                );
+
+                invalidate_snapshot(
+                    &mut ctx.accounts.curve,
+                    &snapshot_info,
+                    snapshot.merkle_root,
+                    &ctx.accounts.challenger,
+                    SnapshotChallenge::NonMembership,
+                    dispute.holder,
+                    clock.unix_timestamp,
+                )?;
+            }
+        }
+
+        // Stake and rent return to the challenger via `close`
+        emit!(SnapshotDisputeResolvedEvent {
+            curve: ctx.accounts.curve.key(),
+            holder: dispute.holder,
+            upheld: true,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Snapshot dispute upheld for: {}", dispute.holder);
+        Ok(())
+    }
+
+    // ============================================================================
+    // V4 LAUNCH SYSTEM
+    // ============================================================================
+
+    /// Launch token (split reserve, emit event for off-chain service)
+    pub fn launch(
+        ctx: Context<LaunchToken>,
+        initial_buy_percentage: u8, // 20-30%
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        // SECURITY: Only creator can launch
+        require!(
+            ctx.accounts.curve.creator == ctx.accounts.creator.key(),
+            CurveError::Unauthorized
+        );
+
+        let split = execute_launch(
+            &mut ctx.accounts.curve,
+            &ctx.accounts.config,
+            &ctx.accounts.snapshot,
+            &ctx.accounts.reserve_vault,
+            [
+                &ctx.accounts.lp_vault,
+                &ctx.accounts.marketing_wallet,
+                &ctx.accounts.utility_wallet,
+            ],
+            initial_buy_percentage,
+            clock.unix_timestamp,
+        )?;
+        let curve = &ctx.accounts.curve;
+
+        // 3. EMIT EVENT FOR OFF-CHAIN SERVICE
+        emit!(CurveLaunchedEvent {
+            curve_id: curve.key(),
+            token_mint: None, // Set by off-chain service after Pump.fun creation
+            snapshot_root: curve.snapshot_root.unwrap(),
+            supply_at_launch: curve.supply_at_freeze,
+            initial_buy_sol: split.initial_buy_sol,
+            marketing_sol: split.marketing_sol,
+            utility_sol: split.utility_sol,
+            lp_vault: ctx.accounts.lp_vault.key(),
+            marketing_wallet: ctx.accounts.marketing_wallet.key(),
+            utility_wallet: ctx.accounts.utility_wallet.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("🚀 LAUNCHED!");
+        msg!("Initial buy: {} SOL ({} %)", split.initial_buy_sol / 1_000_000_000, initial_buy_percentage);
+        msg!("Marketing: {} SOL", split.marketing_sol / 1_000_000_000);
+        msg!("Utility: {} SOL", split.utility_sol / 1_000_000_000);
+        msg!("Off-chain service will now:");
+        msg!("1. Create token on Pump.fun");
+        msg!("2. Buy tokens with LP vault SOL");
+        msg!("3. Fund the airdrop vault (set_token_mint, fund_airdrop)");
+        msg!("4. Key holders can then claim via Merkle proof");
+        Ok(())
+    }
+
+    /// Launch with an SPL mint created by this program (no off-chain service needed)
+    /// Mints TOKEN_TOTAL_SUPPLY: LAUNCH_LIQUIDITY_BPS to the liquidity vault, the rest to the
+    /// airdrop vault for holder claims, then revokes the mint authority.
+    /// The initial-buy SOL stays in the program's lp_vault PDA to seed liquidity.
+    pub fn launch_with_mint(
+        ctx: Context<LaunchWithMint>,
+        initial_buy_percentage: u8, // 20-30%
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        // SECURITY: Input validation
+        TokenMetadata::validate(&name, &symbol, &uri)?;
+
+        let split = execute_launch(
+            &mut ctx.accounts.curve,
+            &ctx.accounts.config,
+            &ctx.accounts.snapshot,
+            &ctx.accounts.reserve_vault,
+            [
+                &ctx.accounts.lp_vault,
+                &ctx.accounts.marketing_wallet,
+                &ctx.accounts.utility_wallet,
+            ],
+            initial_buy_percentage,
+            clock.unix_timestamp,
+        )?;
+
+        let liquidity_amount = (TOKEN_TOTAL_SUPPLY as u128)
+            .checked_mul(LAUNCH_LIQUIDITY_BPS)
+            .ok_or(CurveError::ArithmeticOverflow)?
+            .checked_div(BPS_DENOMINATOR)
+            .ok_or(CurveError::ArithmeticOverflow)? as u64;
+        let airdrop_amount = TOKEN_TOTAL_SUPPLY
+            .checked_sub(liquidity_amount)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+
+        // 1. UPDATE STATE FIRST
+        let token_mint = ctx.accounts.token_mint.key();
+        ctx.accounts.curve.token_mint = Some(token_mint);
+
+        let snapshot = &mut ctx.accounts.snapshot;
+        snapshot.total_token_pool = airdrop_amount;
+        snapshot.vault_bump = ctx.bumps.airdrop_vault;
+
+        let metadata = &mut ctx.accounts.metadata;
+        metadata.mint = token_mint;
+        metadata.curve = ctx.accounts.curve.key();
+        metadata.name = name;
+        metadata.symbol = symbol;
+        metadata.uri = uri;
+        metadata.bump = ctx.bumps.metadata;
+
+        // 2. MINT THE FIXED SUPPLY (curve PDA is the mint authority)
+        let curve = &ctx.accounts.curve;
+        let seeds = &[
+            b"curve",
+            curve.twitter_handle.as_bytes(),
+            &[curve.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        for (vault, amount) in [
+            (ctx.accounts.airdrop_vault.to_account_info(), airdrop_amount),
+            (ctx.accounts.liquidity_vault.to_account_info(), liquidity_amount),
+        ] {
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        to: vault,
+                        authority: curve.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+
+        // Fixed supply: nobody can mint again
+        token::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: curve.to_account_info(),
+                    account_or_mint: ctx.accounts.token_mint.to_account_info(),
+                },
+                signer,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        // 3. EMIT EVENTS
+        emit!(TokenMintedEvent {
+            curve: curve.key(),
+            token_mint,
+            total_supply: TOKEN_TOTAL_SUPPLY,
+            airdrop_amount,
+            liquidity_amount,
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(CurveLaunchedEvent {
+            curve_id: curve.key(),
+            token_mint: Some(token_mint),
+            snapshot_root: curve.snapshot_root.unwrap(),
+            supply_at_launch: curve.supply_at_freeze,
+            initial_buy_sol: split.initial_buy_sol,
+            marketing_sol: split.marketing_sol,
+            utility_sol: split.utility_sol,
+            lp_vault: ctx.accounts.lp_vault.key(),
+            marketing_wallet: ctx.accounts.marketing_wallet.key(),
+            utility_wallet: ctx.accounts.utility_wallet.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("🚀 LAUNCHED with on-chain mint: {}", token_mint);
+        msg!("Airdrop: {} | Liquidity: {}", airdrop_amount, liquidity_amount);
+        Ok(())
+    }
+
+    // ============================================================================
+    // V4 CLAIM SYSTEM
+    // ============================================================================
+
+    /// Record the launched token's mint and open the snapshot's airdrop vault (creator only)
+    pub fn set_token_mint(ctx: Context<SetTokenMint>) -> Result<()> {
+        let curve = &mut ctx.accounts.curve;
+
+        // SECURITY: Reject curves not yet resized by migrate_curve (all-zero fee schedule)
+        curve.fee_schedule.validate()?;
+
+        require!(
+            curve.status == CurveStatus::Launched,
+            CurveError::CurveNotLaunched
+        );
+        require!(curve.token_mint.is_none(), CurveError::TokenMintAlreadySet);
+
+        curve.token_mint = Some(ctx.accounts.token_mint.key());
+        ctx.accounts.snapshot.vault_bump = ctx.bumps.airdrop_vault;
+
+        msg!("Token mint set: {}", ctx.accounts.token_mint.key());
+        msg!("Airdrop vault: {}", ctx.accounts.airdrop_vault.key());
+        Ok(())
+    }
+
+    /// Deposit the holders' token allocation into the airdrop vault (creator only, once)
+    /// Sets the snapshot's total_token_pool, which claims are paid pro-rata from
+    pub fn fund_airdrop(ctx: Context<FundAirdrop>, amount: u64) -> Result<()> {
+        require!(amount > 0, CurveError::InvalidAmount);
+
+        let snapshot = &mut ctx.accounts.snapshot;
+        require!(snapshot.total_token_pool == 0, CurveError::AirdropAlreadyFunded);
+
+        // 1. UPDATE STATE FIRST (CEI pattern)
+        snapshot.total_token_pool = amount;
+
+        // 2. THEN TRANSFER TOKENS
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_token_account.to_account_info(),
+                    to: ctx.accounts.airdrop_vault.to_account_info(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        emit!(AirdropFundedEvent {
+            curve: ctx.accounts.curve.key(),
+            snapshot: snapshot.key(),
+            token_mint: ctx.accounts.airdrop_vault.mint,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("🪂 Airdrop funded: {} tokens", amount);
+        Ok(())
+    }
+
+    /// Claim tokens using Merkle proof
+    /// Transfers the holder's pro-rata share of the airdrop vault to their associated token account
+    pub fn claim_tokens(
+        ctx: Context<ClaimTokens>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let snapshot = &ctx.accounts.snapshot;
+        let holder = &ctx.accounts.key_holder;
+        let claim_record = &mut ctx.accounts.claim_record;
+        let clock = Clock::get()?;
+
+        // SECURITY: Check claims are not paused
+        ctx.accounts.config.require_claims_enabled()?;
+
+        // SECURITY: Root must be settled (verified or past its challenge window)
+        require!(
+            snapshot.is_settled(clock.unix_timestamp),
+            CurveError::SnapshotNotSettled
+        );
+
+        // SECURITY: Prevent double claiming
+        require!(
+            claim_record.amount_claimed == 0,
+            CurveError::AlreadyClaimed
+        );
+
+        require!(snapshot.total_token_pool > 0, CurveError::AirdropNotFunded);
+
+        // Verify Merkle proof
+        let leaf = snapshot_leaf(&holder.owner, holder.amount);
+
+        let is_valid = verify_merkle_proof(&proof, &snapshot.merkle_root, &leaf);
+        require!(is_valid, CurveError::InvalidMerkleProof);
+
+        // Calculate token allocation
+        // (holder's keys / total keys) * total token pool
+        let token_amount = (holder.amount as u128)
             .checked_mul(snapshot.total_token_pool as u128)
             .ok_or(CurveError::ArithmeticOverflow)?
             .checked_div(snapshot.total_supply)
             .ok_or(CurveError::ArithmeticOverflow)? as u64;
+        require!(token_amount > 0, CurveError::NothingToClaim);
+
+        // 1. UPDATE STATE FIRST (CEI pattern)
+        claim_record.snapshot = snapshot.key();
+        claim_record.holder = holder.owner;
+        claim_record.amount_claimed = token_amount;
+        claim_record.claimed_at = clock.unix_timestamp;
+        claim_record.bump = ctx.bumps.claim_record;
+
+        // 2. THEN TRANSFER TOKENS (airdrop vault is owned by the snapshot PDA)
+        let seeds = &[
+            b"snapshot",
+            snapshot.curve.as_ref(),
+            &[snapshot.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.airdrop_vault.to_account_info(),
+                    to: ctx.accounts.holder_token_account.to_account_info(),
+                    authority: snapshot.to_account_info(),
+                },
+                signer,
+            ),
+            token_amount,
+        )?;
+
+        // Emit event
+        emit!(TokensClaimedEvent {
+            snapshot: snapshot.key(),
+            holder: holder.owner,
+            keys_held: holder.amount,
+            tokens_claimed: token_amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("✅ Tokens claimed: {}", token_amount);
+        msg!("Keys held: {}", holder.amount);
+        Ok(())
+    }
+
+    // ============================================================================
+    // REFUND SYSTEM
+    // ============================================================================
+
+    /// Put a frozen curve that never launched into refund mode
+    /// Admin can do this any time after freeze; anyone once `config.refund_delay` has passed
+    pub fn start_refund(ctx: Context<StartRefund>) -> Result<()> {
+        let curve = &mut ctx.accounts.curve;
+        let config = &ctx.accounts.config;
+        let clock = Clock::get()?;
+
+        // SECURITY: Reject curves not yet resized by migrate_curve (all-zero fee schedule)
+        curve.fee_schedule.validate()?;
+
+        // SECURITY: Only frozen curves that have not started launching
+        require!(
+            curve.status == CurveStatus::Frozen,
+            CurveError::CurveNotFrozen
+        );
+        require!(!curve.is_launching, CurveError::AlreadyLaunched);
+
+        let by_admin = ctx.accounts.caller.key() == config.authority;
+        require!(
+            by_admin || curve.is_refund_deadline_passed(config.refund_delay, clock.unix_timestamp),
+            CurveError::RefundDeadlineNotReached
+        );
+
+        curve.status = CurveStatus::Refunding;
+
+        emit!(CurveRefundingEvent {
+            curve: curve.key(),
+            started_by: ctx.accounts.caller.key(),
+            by_admin,
+            reserve: curve.reserve_at_freeze,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("💸 Curve {} REFUNDING | Reserve: {} lamports", curve.twitter_handle, curve.reserve_at_freeze);
+        Ok(())
+    }
+
+    /// Redeem all of a holder's keys pro-rata against the reserve at freeze
+    /// Closes the KeyHolder, returning its rent to the holder
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        // SECURITY: Check claims are not paused
+        ctx.accounts.config.require_claims_enabled()?;
+
+        let curve = &mut ctx.accounts.curve;
+        curve.fee_schedule.validate()?;
+        require!(
+            curve.status == CurveStatus::Refunding,
+            CurveError::RefundNotActive
+        );
+
+        let keys = ctx.accounts.key_holder.amount;
+        let refund = curve.calculate_refund(keys)?;
+
+        // 1. UPDATE STATE FIRST (CEI pattern)
+        curve.supply = curve
+            .supply
+            .checked_sub(keys as u128)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+
+        curve.reserve_balance = curve
+            .reserve_balance
+            .checked_sub(refund)
+            .ok_or(CurveError::InsufficientReserve)?;
+
+        // 2. THEN MOVE LAMPORTS (reserve vault is program-owned)
+        if refund > 0 {
+            ctx.accounts.reserve_vault.sub_lamports(to_lamports(refund)?)?;
+            ctx.accounts.holder.add_lamports(to_lamports(refund)?)?;
+        }
+
+        emit!(RefundClaimedEvent {
+            curve: curve.key(),
+            holder: ctx.accounts.holder.key(),
+            keys,
+            amount: refund,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Refund claimed: {} keys for {} lamports", keys, refund);
+        Ok(())
+    }
+
+    // ============================================================================
+    // LIQUIDITY POOL (graduated tokens)
+    // ============================================================================
+
+    /// Seed the constant-product pool from a launch_with_mint launch (permissionless)
+    /// Uses the lp_vault SOL (initial_buy_amount) and the liquidity_vault tokens
+    /// Seed LP is never minted, so the initial liquidity is locked forever
+    pub fn create_pool(ctx: Context<CreatePool>) -> Result<()> {
+        let clock = Clock::get()?;
+        let curve = &ctx.accounts.curve;
+
+        require!(
+            curve.status == CurveStatus::Launched,
+            CurveError::CurveNotLaunched
+        );
+
+        let sol_reserve = to_lamports(curve.initial_buy_amount)?;
+        let token_reserve = ctx.accounts.liquidity_vault.amount;
+
+        // SECURITY: Seed SOL must actually be in the vault (above its rent)
+        let rent = Rent::get()?.minimum_balance(ctx.accounts.lp_vault.data_len());
+        require!(
+            ctx.accounts.lp_vault.lamports() >= rent.saturating_add(sol_reserve),
+            CurveError::InsufficientLiquidity
+        );
+        require!(sol_reserve > 0 && token_reserve > 0, CurveError::InsufficientLiquidity);
+
+        let locked_liquidity = amm::calculate_initial_liquidity(sol_reserve, token_reserve)?;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.curve = curve.key();
+        pool.token_mint = ctx.accounts.token_mint.key();
+        pool.lp_mint = ctx.accounts.lp_mint.key();
+        pool.sol_reserve = sol_reserve;
+        pool.token_reserve = token_reserve;
+        pool.lp_supply = locked_liquidity;
+        pool.locked_liquidity = locked_liquidity;
+        pool.created_at = clock.unix_timestamp;
+        pool.bump = ctx.bumps.pool;
+        pool.sol_vault_bump = ctx.bumps.lp_vault;
+        pool.token_vault_bump = ctx.bumps.liquidity_vault;
+
+        emit!(PoolCreatedEvent {
+            curve: curve.key(),
+            pool: pool.key(),
+            token_mint: pool.token_mint,
+            lp_mint: pool.lp_mint,
+            sol_reserve,
+            token_reserve,
+            locked_liquidity,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Pool created: {} lamports / {} tokens", sol_reserve, token_reserve);
+        Ok(())
+    }
+
+    /// Swap an exact input through the pool (SOL -> token if `sol_to_token`, else token -> SOL)
+    /// SECURITY: Reverts if output is below `min_amount_out` or `deadline` has passed
+    pub fn swap(
+        ctx: Context<PoolSwap>,
+        amount_in: u64,
+        min_amount_out: u64,
+        sol_to_token: bool,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        // SECURITY: Reject stale transactions
+        check_deadline(deadline)?;
+
+        // SECURITY: Check trading is not paused (globally or for this curve)
+        ctx.accounts.config.require_trading_enabled()?;
+        ctx.accounts.curve.require_not_paused()?;
+
+        require!(amount_in > 0, CurveError::InvalidAmount);
+
+        let pool = &mut ctx.accounts.pool;
+        let (reserve_in, reserve_out) = if sol_to_token {
+            (pool.sol_reserve, pool.token_reserve)
+        } else {
+            (pool.token_reserve, pool.sol_reserve)
+        };
+
+        let amount_out = amm::calculate_swap_output(amount_in, reserve_in, reserve_out)?;
+        require!(
+            amount_out > 0 && amount_out >= min_amount_out,
+            CurveError::PoolSlippageExceeded
+        );
+
+        // 1. UPDATE STATE FIRST (CEI pattern)
+        let reserve_in = reserve_in
+            .checked_add(amount_in)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+        let reserve_out = reserve_out
+            .checked_sub(amount_out)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+        if sol_to_token {
+            pool.sol_reserve = reserve_in;
+            pool.token_reserve = reserve_out;
+        } else {
+            pool.token_reserve = reserve_in;
+            pool.sol_reserve = reserve_out;
+        }
+
+        // 2. THEN TRANSFER
+        let curve = &ctx.accounts.curve;
+        let seeds = &[
+            b"curve",
+            curve.twitter_handle.as_bytes(),
+            &[curve.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        if sol_to_token {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.user.to_account_info(),
+                        to: ctx.accounts.lp_vault.to_account_info(),
+                    },
+                ),
+                amount_in,
+            )?;
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.liquidity_vault.to_account_info(),
+                        to: ctx.accounts.user_token_account.to_account_info(),
+                        authority: curve.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount_out,
+            )?;
+        } else {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.user_token_account.to_account_info(),
+                        to: ctx.accounts.liquidity_vault.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                amount_in,
+            )?;
+            // lp_vault is program-owned, no CPI (moved after the token CPI)
+            ctx.accounts.lp_vault.sub_lamports(amount_out)?;
+            ctx.accounts.user.add_lamports(amount_out)?;
+        }
+
+        let pool = &ctx.accounts.pool;
+        emit!(PoolSwapEvent {
+            pool: pool.key(),
+            user: ctx.accounts.user.key(),
+            sol_to_token,
+            amount_in,
+            amount_out,
+            sol_reserve: pool.sol_reserve,
+            token_reserve: pool.token_reserve,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Swapped {} for {}", amount_in, amount_out);
+        Ok(())
+    }
+
+    /// Add liquidity at the current ratio: deposit `sol_amount` plus the matching tokens
+    /// SECURITY: Reverts if the matching tokens exceed `max_token_amount` or `deadline` has passed
+    pub fn add_liquidity(
+        ctx: Context<AddLiquidity>,
+        sol_amount: u64,
+        max_token_amount: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        // SECURITY: Reject stale transactions
+        check_deadline(deadline)?;
+
+        // SECURITY: Check trading is not paused (globally or for this curve)
+        ctx.accounts.config.require_trading_enabled()?;
+        ctx.accounts.curve.require_not_paused()?;
+
+        require!(sol_amount > 0, CurveError::InvalidAmount);
+
+        let pool = &mut ctx.accounts.pool;
+        let (token_amount, lp_amount) = amm::calculate_deposit(
+            sol_amount,
+            pool.sol_reserve,
+            pool.token_reserve,
+            pool.lp_supply,
+        )?;
+        require!(lp_amount > 0, CurveError::InvalidAmount);
+        require!(
+            token_amount <= max_token_amount,
+            CurveError::PoolSlippageExceeded
+        );
+
+        // 1. UPDATE STATE FIRST (CEI pattern)
+        pool.sol_reserve = pool
+            .sol_reserve
+            .checked_add(sol_amount)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+        pool.token_reserve = pool
+            .token_reserve
+            .checked_add(token_amount)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+        pool.lp_supply = pool
+            .lp_supply
+            .checked_add(lp_amount)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+
+        // 2. THEN TRANSFER AND MINT LP
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: ctx.accounts.lp_vault.to_account_info(),
+                },
+            ),
+            sol_amount,
+        )?;
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    to: ctx.accounts.liquidity_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            token_amount,
+        )?;
+
+        let pool = &ctx.accounts.pool;
+        let curve_key = ctx.accounts.curve.key();
+        let seeds = &[b"pool", curve_key.as_ref(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.user_lp_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            lp_amount,
+        )?;
+
+        emit!(PoolLiquidityEvent {
+            pool: pool.key(),
+            user: ctx.accounts.user.key(),
+            added: true,
+            sol_amount,
+            token_amount,
+            lp_amount,
+            lp_supply: pool.lp_supply,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Liquidity added: {} lamports + {} tokens for {} LP", sol_amount, token_amount, lp_amount);
+        Ok(())
+    }
+
+    /// Burn LP tokens for a pro-rata share of both reserves
+    /// Stays open while trading is paused so providers can always exit
+    /// SECURITY: Reverts if either output is below its minimum or `deadline` has passed
+    pub fn remove_liquidity(
+        ctx: Context<RemoveLiquidity>,
+        lp_amount: u64,
+        min_sol_amount: u64,
+        min_token_amount: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        // SECURITY: Reject stale transactions
+        check_deadline(deadline)?;
+
+        require!(lp_amount > 0, CurveError::InvalidAmount);
+
+        let pool = &mut ctx.accounts.pool;
+        let (sol_amount, token_amount) = amm::calculate_withdrawal(
+            lp_amount,
+            pool.sol_reserve,
+            pool.token_reserve,
+            pool.lp_supply,
+        )?;
+        require!(
+            sol_amount >= min_sol_amount && token_amount >= min_token_amount,
+            CurveError::PoolSlippageExceeded
+        );
+
+        // 1. UPDATE STATE FIRST (CEI pattern)
+        pool.sol_reserve = pool
+            .sol_reserve
+            .checked_sub(sol_amount)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+        pool.token_reserve = pool
+            .token_reserve
+            .checked_sub(token_amount)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+        pool.lp_supply = pool
+            .lp_supply
+            .checked_sub(lp_amount)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+
+        // 2. BURN LP, THEN PAY OUT
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    from: ctx.accounts.user_lp_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            lp_amount,
+        )?;
+
+        let curve = &ctx.accounts.curve;
+        let seeds = &[
+            b"curve",
+            curve.twitter_handle.as_bytes(),
+            &[curve.bump],
+        ];
+        let signer = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.liquidity_vault.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: curve.to_account_info(),
+                },
+                signer,
+            ),
+            token_amount,
+        )?;
+
+        // lp_vault is program-owned, no CPI (moved after the token CPIs)
+        ctx.accounts.lp_vault.sub_lamports(sol_amount)?;
+        ctx.accounts.user.add_lamports(sol_amount)?;
+
+        let pool = &ctx.accounts.pool;
+        emit!(PoolLiquidityEvent {
+            pool: pool.key(),
+            user: ctx.accounts.user.key(),
+            added: false,
+            sol_amount,
+            token_amount,
+            lp_amount,
+            lp_supply: pool.lp_supply,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Liquidity removed: {} LP for {} lamports + {} tokens", lp_amount, sol_amount, token_amount);
+        Ok(())
+    }
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// SECURITY: Reject the transaction if it lands after the signer's deadline
+fn check_deadline(deadline: Option<i64>) -> Result<()> {
+    if let Some(deadline) = deadline {
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp <= deadline,
+            CurveError::DeadlineExceeded
+        );
+    }
+    Ok(())
+}
+
+/// V6: Resolve the trade's referrer (code owner) from the holder's sticky binding
+/// The binding is recorded from the passed referral code on the holder's first buy
+/// and never changes; later trades must pass the bound code, or none if unbound
+fn resolve_referrer(
+    key_holder: &mut KeyHolder,
+    referral_code: &mut Option<Account<Referrer>>,
+    trader: &Pubkey,
+    is_first_buy: bool,
+) -> Result<Option<Pubkey>> {
+    let code = match referral_code.as_mut() {
+        Some(code) => code,
+        None => {
+            require!(key_holder.referrer.is_none(), CurveError::ReferrerMismatch);
+            return Ok(None);
+        }
+    };
+
+    match key_holder.referrer {
+        Some(bound) => {
+            require_keys_eq!(code.key(), bound, CurveError::ReferrerMismatch);
+        }
+        None => {
+            // SECURITY: Binding only happens once, on the first buy
+            require!(is_first_buy, CurveError::ReferrerMismatch);
+            require!(code.owner != *trader, CurveError::SelfReferral);
+
+            key_holder.referrer = Some(code.key());
+            code.referred_holders = code
+                .referred_holders
+                .checked_add(1)
+                .ok_or(CurveError::ArithmeticOverflow)?;
+        }
+    }
+
+    Ok(Some(code.owner))
+}
+
+/// Credit a referral fee to the bound code's unclaimed balance and lifetime stats
+fn credit_referral(referral_code: &mut Option<Account<Referrer>>, amount: u128) -> Result<()> {
+    if let Some(code) = referral_code.as_mut() {
+        code.total_earned = code
+            .total_earned
+            .checked_add(amount)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+        code.unclaimed_fees = code
+            .unclaimed_fees
+            .checked_add(amount)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+    }
+    Ok(())
+}
+
+/// Take a buyer's payment in one system transfer into the reserve vault,
+/// then move the fee share on to the fee vault (both program-owned)
+fn deposit_trade<'info>(
+    buyer: &Signer<'info>,
+    reserve_vault: &AccountInfo<'info>,
+    fee_vault: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    reserve_amount: u128,
+    fees_total: u128,
+) -> Result<()> {
+    let total = reserve_amount
+        .checked_add(fees_total)
+        .ok_or(CurveError::ArithmeticOverflow)?;
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: buyer.to_account_info(),
+                to: reserve_vault.clone(),
+            },
+        ),
+        to_lamports(total)?,
+    )?;
+
+    reserve_vault.sub_lamports(to_lamports(fees_total)?)?;
+    fee_vault.add_lamports(to_lamports(fees_total)?)?;
+    Ok(())
+}
+
+/// Narrow a u128 lamport amount for transfers
+fn to_lamports(amount: u128) -> Result<u64> {
+    u64::try_from(amount).map_err(|_| error!(CurveError::ArithmeticOverflow))
+}
+
+/// Close a program-owned account, sending its whole balance (including rent) to `destination`
+fn close_vault<'info>(vault: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+    let lamports = vault.lamports();
+    vault.sub_lamports(lamports)?;
+    destination.add_lamports(lamports)?;
+
+    vault.assign(&anchor_lang::system_program::ID);
+    vault.realloc(0, false)?;
+    Ok(())
+}
+
+/// Close a cancelled curve's access list, access entries and referral codes
+/// Access accounts refund the creator; each referral code is followed by its owner
+fn close_curve_accounts<'info>(
+    curve: &Pubkey,
+    accounts: &[AccountInfo<'info>],
+    creator: &AccountInfo<'info>,
+) -> Result<()> {
+    let mut accounts = accounts.iter();
+    while let Some(info) = accounts.next() {
+        require!(info.owner == &crate::ID, CurveError::InvalidCancelAccounts);
+
+        let destination = {
+            let data = info.try_borrow_data()?;
+            require!(data.len() >= 8, CurveError::InvalidCancelAccounts);
+
+            if data[..8] == <CurveAccessList as anchor_lang::Discriminator>::DISCRIMINATOR {
+                let list = CurveAccessList::try_deserialize(&mut &data[..])?;
+                require_keys_eq!(list.curve, *curve, CurveError::InvalidCancelAccounts);
+                creator
+            } else if data[..8] == <CurveAccessEntry as anchor_lang::Discriminator>::DISCRIMINATOR {
+                let entry = CurveAccessEntry::try_deserialize(&mut &data[..])?;
+                require_keys_eq!(entry.curve, *curve, CurveError::InvalidCancelAccounts);
+                creator
+            } else if data[..8] == <Referrer as anchor_lang::Discriminator>::DISCRIMINATOR {
+                let referrer = Referrer::try_deserialize(&mut &data[..])?;
+                require_keys_eq!(referrer.curve, *curve, CurveError::InvalidCancelAccounts);
+
+                let owner = accounts.next().ok_or(CurveError::InvalidCancelAccounts)?;
+                require_keys_eq!(owner.key(), referrer.owner, CurveError::InvalidCancelAccounts);
+                owner
+            } else {
+                return err!(CurveError::InvalidCancelAccounts);
+            }
+        };
+
+        close_vault(info, destination)?;
+    }
+    Ok(())
+}
+
+/// Resize a program-owned account, topping up rent from `payer` when it grows
+fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(new_len);
+    let shortfall = rent.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    account.realloc(new_len, true)?;
+    Ok(())
+}
+
+/// Queue an admin action behind `config.timelock_delay`
+fn queue_action(ctx: Context<QueueAdminAction>, action: AdminActionKind) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    require!(
+        config.authority == ctx.accounts.authority.key(),
+        CurveError::Unauthorized
+    );
+
+    // Reject invalid changes now rather than after the delay
+    let mut preview = config.clone().into_inner();
+    apply_admin_action(&mut preview, &action, None)?;
+
+    let clock = Clock::get()?;
+    let executable_at = clock
+        .unix_timestamp
+        .checked_add(config.timelock_delay)
+        .ok_or(CurveError::ArithmeticOverflow)?;
+
+    let pending = &mut ctx.accounts.pending_action;
+    pending.id = config.admin_action_nonce;
+    pending.action = action;
+    pending.proposer = ctx.accounts.authority.key();
+    pending.queued_at = clock.unix_timestamp;
+    pending.executable_at = executable_at;
+    pending.bump = ctx.bumps.pending_action;
+
+    config.admin_action_nonce = config
+        .admin_action_nonce
+        .checked_add(1)
+        .ok_or(CurveError::ArithmeticOverflow)?;
+
+    emit!(AdminActionQueuedEvent {
+        id: pending.id,
+        action,
+        proposer: pending.proposer,
+        executable_at,
+    });
+
+    msg!("Admin action #{} queued, executable at {}", pending.id, executable_at);
+    Ok(())
+}
+
+/// Apply a timelocked admin action to the config, emitting its change event
+/// `now` is None when previewing at queue time (events are not emitted then)
+fn apply_admin_action(
+    config: &mut CurveConfig,
+    action: &AdminActionKind,
+    now: Option<i64>,
+) -> Result<()> {
+    match *action {
+        AdminActionKind::UpdateWallets(update) => {
+            let old_config = config.params();
+            config.apply_wallet_update(&update);
+
+            if let Some(timestamp) = now {
+                emit!(ConfigUpdatedEvent {
+                    old_config,
+                    new_config: config.params(),
+                    timestamp,
+                });
+            }
+        }
+        AdminActionKind::UpdateFeeSchedule(fee_schedule) => {
+            // Existing curves keep the schedule recorded at their creation
+            fee_schedule.validate()?;
+
+            let old_schedule = config.fee_schedule;
+            config.fee_schedule = fee_schedule;
+
+            if let Some(timestamp) = now {
+                emit!(FeeScheduleUpdatedEvent {
+                    old_schedule,
+                    new_schedule: fee_schedule,
+                    timestamp,
+                });
+            }
+        }
+        AdminActionKind::ProposeAuthority(new_authority) => {
+            // Step 1 of 2, the proposed authority still has to accept_authority
+            config.pending_authority = new_authority;
+
+            if let Some(timestamp) = now {
+                emit!(AuthorityProposedEvent {
+                    authority: config.authority,
+                    pending_authority: new_authority,
+                    timestamp,
+                });
+            }
+        }
+        AdminActionKind::SetTimelockDelay(delay) => {
+            // SECURITY: A short delay would let a compromised key skip the review window
+            require!(delay >= ADMIN_TIMELOCK_DEFAULT, CurveError::InvalidConfig);
+            config.timelock_delay = delay;
+        }
+        AdminActionKind::SetRefundDelay(delay) => {
+            // SECURITY: start_refund reads this live, so it only changes after the review window
+            require!(delay > 0, CurveError::InvalidConfig);
+
+            let old_config = config.params();
+            config.refund_delay = delay;
+
+            if let Some(timestamp) = now {
+                emit!(ConfigUpdatedEvent {
+                    old_config,
+                    new_config: config.params(),
+                    timestamp,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// How a buy order was sized by the buyer
+#[derive(Clone, Copy)]
+enum BuyOrder {
+    /// Exact key count, reverts above `max_total_cost`
+    ExactKeys { amount: u64, max_total_cost: u64 },
+    /// Exact lamport budget, reverts below `min_keys`
+    ExactIn { max_lamports: u64, min_keys: u64 },
+}
+
+/// Shared buy path for `buy_keys` and `buy_keys_exact_in`
+fn execute_buy(
+    ctx: Context<BuyKeys>,
+    order: BuyOrder,
+) -> Result<()> {
+    // Manually deserialize config to reduce stack usage
+    // (try_deserialize checks the discriminator, so it gets the full buffer)
+    let config_data = ctx.accounts.config.try_borrow_data()?;
+    let config = CurveConfig::try_deserialize(&mut &config_data[..])?;
+
+    // SECURITY: Check trading is not paused (globally or for this curve)
+    config.require_trading_enabled()?;
+    ctx.accounts.curve.require_not_paused()?;
+
+    // SECURITY: Unmigrated curves decode with an all-zero fee schedule (free keys)
+    ctx.accounts.curve.fee_schedule.validate()?;
+
+    // SECURITY: Reentrancy check
+    require!(ctx.accounts.curve.check_reentrancy(), CurveError::ReentrancyDetected);
+    ctx.accounts.curve.set_reentrancy(true);
+
+    // SECURITY: Check curve is ACTIVE (not Frozen or Launched)
+    require!(
+        ctx.accounts.curve.status == CurveStatus::Active,
+        CurveError::TradingDisabled
+    );
+
+    // SECURITY: Check buyer is not banned or flagged pending review
+    require!(
+        !BanRecord::exists(&ctx.accounts.ban_record),
+        CurveError::AccountBanned
+    );
+    require!(
+        !BotReport::is_flagged(&ctx.accounts.bot_report)?,
+        CurveError::AccountFlagged
+    );
+
+    // SECURITY: Check the curve creator's access list (deny entries, allowlist phase)
+    CurveAccessList::check_buyer(&ctx.accounts.access_list, &ctx.accounts.access_entry)?;
+
+    // Size the order: exact key count, or the most keys the budget affords
+    let amount = match order {
+        BuyOrder::ExactKeys { amount, .. } => amount,
+        BuyOrder::ExactIn { max_lamports, min_keys } => {
+            let remaining_cap = ctx.accounts.curve
+                .get_max_keys_per_wallet()
+                .saturating_sub(ctx.accounts.key_holder.amount);
+
+            let keys = math::calculate_keys_for_budget(
+                ctx.accounts.curve.supply,
+                max_lamports as u128,
+                remaining_cap.min(config.max_purchase),
+            )?;
+
+            require!(keys >= min_keys, CurveError::BelowMinKeys);
+            keys
+        }
+    };
+
+    // SECURITY: Input validation
+    ctx.accounts.curve.validate_amount(amount, config.max_purchase)?;
+
+    // SECURITY: Validate key cap (1% of supply, min 20, max 100)
+    ctx.accounts.curve.validate_key_cap(ctx.accounts.key_holder.amount, amount)?;
+
+    // Resolve referrer from the holder's binding, binding the passed code on first buy
+    let is_first_buy = ctx.accounts.key_holder.owner == Pubkey::default();
+    let referrer = resolve_referrer(
+        &mut ctx.accounts.key_holder,
+        &mut ctx.accounts.referral_code,
+        &ctx.accounts.buyer.key(),
+        is_first_buy,
+    )?;
+
+    // V6: Derive fee routing on-chain (user referral, project self-referral, or none)
+    let referrer_type = math::ReferrerType::resolve(referrer, &ctx.accounts.curve.creator);
+
+    // Calculate price and fees (minimize stack usage)
+    let total_cost = ctx.accounts.curve.calculate_buy_price(amount)?;
+    let fees = math::calculate_v6_buy_fees(
+        total_cost,
+        referrer_type,
+        &ctx.accounts.curve.fee_schedule,
+    )?;
+
+    // SECURITY: Slippage protection (price may have moved since signing)
+    // ExactIn orders are sized from the budget, so they can never exceed it
+    if let BuyOrder::ExactKeys { max_total_cost, .. } = order {
+        require!(
+            total_cost <= max_total_cost as u128,
+            CurveError::SlippageExceeded
+        );
+    }
+
+    // 1. UPDATE STATE FIRST (CEI pattern)
+    ctx.accounts.curve.supply = ctx.accounts.curve
+        .supply
+        .checked_add(amount as u128)
+        .ok_or(CurveError::ArithmeticOverflow)?;
+
+    ctx.accounts.curve.reserve_balance = ctx.accounts.curve
+        .reserve_balance
+        .checked_add(fees.reserve)
+        .ok_or(CurveError::ArithmeticOverflow)?;
+
+    // Project bucket goes to platform_treasury (creator share only on self-referrals)
+    let (creator_fee, treasury_fee) = math::split_project_bucket(
+        total_cost,
+        fees.project,
+        referrer_type,
+        &ctx.accounts.curve.fee_schedule,
+    )?;
+    ctx.accounts.curve.creator_fees_collected = ctx.accounts.curve
+        .creator_fees_collected
+        .checked_add(creator_fee)
+        .ok_or(CurveError::ArithmeticOverflow)?;
+
+    // Referral earnings accrue to the code's ledger
+    credit_referral(&mut ctx.accounts.referral_code, fees.referral)?;
+
+    ctx.accounts.curve.rewards_fees_collected = ctx.accounts.curve
+        .rewards_fees_collected
+        .checked_add(fees.buyback_burn)
+        .ok_or(CurveError::ArithmeticOverflow)?
+        .checked_add(fees.community_rewards)
+        .ok_or(CurveError::ArithmeticOverflow)?;
+
+    ctx.accounts.curve.total_buys = ctx.accounts.curve
+        .total_buys
+        .checked_add(1)
+        .ok_or(CurveError::ArithmeticOverflow)?;
+
+    // Update or create key holder
+    if ctx.accounts.key_holder.amount == 0 {
+        let clock = Clock::get()?;
+        ctx.accounts.key_holder.owner = ctx.accounts.buyer.key();
+        ctx.accounts.key_holder.curve = ctx.accounts.curve.key();
+        ctx.accounts.key_holder.amount = amount;
+        ctx.accounts.key_holder.acquired_at = clock.unix_timestamp;
+        ctx.accounts.key_holder.is_creator = false;
+        ctx.accounts.key_holder.bump = ctx.bumps.key_holder;
+
+        // Increment unique holders count
+        ctx.accounts.curve.unique_holders = ctx.accounts.curve
+            .unique_holders
+            .checked_add(1)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+    } else {
+        ctx.accounts.key_holder.amount = ctx.accounts.key_holder
+            .amount
+            .checked_add(amount)
+            .ok_or(CurveError::ArithmeticOverflow)?;
+    }
+
+    // Accrue fees to the vault ledgers
+    let fees_total = ctx.accounts.fee_vault.accrue(
+        creator_fee,
+        treasury_fee,
+        fees.buyback_burn,
+        fees.community_rewards,
+        fees.referral,
+    )?;
+
+    // 2. THEN DO EXTERNAL CALLS - single transfer, fees moved on to the fee vault
+    deposit_trade(
+        &ctx.accounts.buyer,
+        &ctx.accounts.reserve_vault,
+        &ctx.accounts.fee_vault.to_account_info(),
+        &ctx.accounts.system_program,
+        fees.reserve,
+        fees_total,
+    )?;
+
+    // 3. CLEAR REENTRANCY GUARD
+    ctx.accounts.curve.set_reentrancy(false);
+
+    // Emit event
+    emit!(KeysPurchasedEvent {
+        curve: ctx.accounts.curve.key(),
+        buyer: ctx.accounts.buyer.key(),
+        amount,
+        cost: total_cost,
+        referrer,
+        referrer_type,
+        fees,
+    });
+
+    // Auto-freeze on the trade that crosses the target reserve
+    if ctx.accounts.curve.is_reserve_threshold_met() {
+        freeze_and_emit(
+            &mut ctx.accounts.curve,
+            FreezeTrigger::ReserveThreshold,
+            Clock::get()?.unix_timestamp,
+        )?;
+    }
+
+    msg!("Keys purchased: {} for {} lamports", amount, total_cost);
+    Ok(())
+}
+
+/// Freeze a curve and emit CurveFrozenEvent
+fn freeze_and_emit(curve: &mut Account<BondingCurve>, trigger: FreezeTrigger, now: i64) -> Result<()> {
+    curve.execute_freeze(trigger, now)?;
+
+    emit!(CurveFrozenEvent {
+        curve_id: curve.key(),
+        trigger,
+        supply: curve.supply_at_freeze,
+        reserve: curve.reserve_at_freeze,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+/// SOL split of a launch (lamports)
+struct LaunchSplit {
+    initial_buy_sol: u128,
+    marketing_sol: u128,
+    utility_sol: u128,
+}
+
+/// Shared launch checks, state updates and reserve split (launch and launch_with_mint)
+/// `wallets` are the LP vault, marketing wallet and utility wallet, in that order
+fn execute_launch<'info>(
+    curve: &mut Account<'info, BondingCurve>,
+    config: &CurveConfig,
+    snapshot: &Snapshot,
+    reserve_vault: &AccountInfo<'info>,
+    wallets: [&AccountInfo<'info>; 3],
+    initial_buy_percentage: u8,
+    now: i64,
+) -> Result<LaunchSplit> {
+    let [lp_vault, marketing_wallet, utility_wallet] = wallets;
+
+    // SECURITY: Reject curves not yet resized by migrate_curve (all-zero fee schedule)
+    curve.fee_schedule.validate()?;
+
+    // SECURITY: Check launches are not paused (globally or for this curve)
+    config.require_launches_enabled()?;
+    curve.require_not_paused()?;
+
+    // SECURITY: Must be frozen and have snapshot
+    require!(curve.can_launch(), CurveError::CurveNotFrozen);
+
+    // SECURITY: Snapshot root must be verified or past its challenge window
+    require!(snapshot.is_settled(now), CurveError::SnapshotNotSettled);
+
+    // SECURITY: Prevent double launch
+    require!(!curve.is_launching, CurveError::AlreadyLaunched);
+    curve.is_launching = true;
+
+    // Validate percentage (20-30%)
+    require!(
+        (20..=30).contains(&initial_buy_percentage),
+        CurveError::InvalidAmount
+    );
+
+    let total_reserve = curve.reserve_at_freeze;
+
+    // Calculate splits
+    let initial_buy_sol = total_reserve
+        .checked_mul(initial_buy_percentage as u128)
+        .ok_or(CurveError::ArithmeticOverflow)?
+        .checked_div(100)
+        .ok_or(CurveError::ArithmeticOverflow)?;
+
+    let marketing_sol = total_reserve
+        .checked_mul(50)
+        .ok_or(CurveError::ArithmeticOverflow)?
+        .checked_div(100)
+        .ok_or(CurveError::ArithmeticOverflow)?;
+
+    let utility_sol = total_reserve
+        .checked_sub(initial_buy_sol)
+        .ok_or(CurveError::ArithmeticOverflow)?
+        .checked_sub(marketing_sol)
+        .ok_or(CurveError::ArithmeticOverflow)?;
+
+    // SECURITY: Check reserve has enough funds
+    require!(
+        curve.reserve_balance >= total_reserve,
+        CurveError::InsufficientReserve
+    );
+
+    // 1. UPDATE STATE FIRST
+    curve.status = CurveStatus::Launched;
+    curve.launched_at = Some(now);
+    curve.initial_buy_amount = initial_buy_sol;
+    curve.lp_vault = Some(lp_vault.key());
+    curve.marketing_wallet = Some(marketing_wallet.key());
+    curve.utility_wallet = Some(utility_wallet.key());
+
+    // Reduce reserve balance
+    curve.reserve_balance = curve
+        .reserve_balance
+        .checked_sub(total_reserve)
+        .ok_or(CurveError::ArithmeticOverflow)?;
+
+    // 2. TRANSFER FUNDS FROM RESERVE (reserve vault is program-owned, no CPI)
+    for (wallet, amount) in [
+        (lp_vault, initial_buy_sol),
+        (marketing_wallet, marketing_sol),
+        (utility_wallet, utility_sol),
+    ] {
+        reserve_vault.sub_lamports(to_lamports(amount)?)?;
+        wallet.add_lamports(to_lamports(amount)?)?;
+    }
+
+    Ok(LaunchSplit {
+        initial_buy_sol,
+        marketing_sol,
+        utility_sol,
+    })
+}
+
+/// Throw out a creator-submitted snapshot, closing it so a new one can be created
+fn invalidate_snapshot<'info>(
+    curve: &mut Account<'info, BondingCurve>,
+    snapshot: &AccountInfo<'info>,
+    merkle_root: [u8; 32],
+    challenger: &AccountInfo<'info>,
+    challenge: SnapshotChallenge,
+    holder: Pubkey,
+    now: i64,
+) -> Result<()> {
+    // SECURITY: Reject curves not yet resized by migrate_curve (all-zero fee schedule)
+    curve.fee_schedule.validate()?;
+
+    curve.snapshot_root = None;
+    close_vault(snapshot, challenger)?;
+
+    emit!(SnapshotInvalidatedEvent {
+        curve: curve.key(),
+        merkle_root,
+        challenge,
+        holder,
+        challenger: challenger.key(),
+        timestamp: now,
+    });
+
+    msg!("❌ Snapshot invalidated ({:?}) for holder: {}", challenge, holder);
+    Ok(())
+}
+
+/// Verify Merkle proof
+fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: &[u8; 32]) -> bool {
+    let mut computed_hash = *leaf;
+
+    for proof_element in proof.iter() {
+        computed_hash = hash_sorted_pair(&computed_hash, proof_element);
+    }
+
+    computed_hash == *root
+}
+
+// ============================================================================
+// CONTEXT STRUCTS
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = CurveConfig::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, CurveConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeBanList<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = BanList::LEN,
+        seeds = [b"ban_list"],
+        bump
+    )]
+    pub ban_list: Account<'info, BanList>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(twitter_handle: String)]
+pub struct CreateCurve<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = BondingCurve::LEN,
+        seeds = [b"curve", twitter_handle.as_bytes()],
+        bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8, // Empty PDA for reserve vault
+        seeds = [b"reserve", curve.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Reserve vault PDA
+    pub reserve_vault: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = FeeVault::LEN,
+        seeds = [b"fee_vault", curve.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, CurveConfig>,
+
+    /// CHECK: Creator's BanRecord PDA (banned iff it exists)
+    #[account(seeds = [b"ban", creator.key().as_ref()], bump)]
+    pub ban_record: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreatorInitialBuy<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve", curve.key().as_ref()],
+        bump = curve.reserve_bump
+    )]
+    /// CHECK: Reserve vault PDA
+    pub reserve_vault: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = KeyHolder::LEN,
+        seeds = [b"holder", curve.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault", curve.key().as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, CurveConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ActivateCurve<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(mut, address = curve.creator)]
+    pub creator: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, CurveConfig>,
+}
+
+#[derive(Accounts)]
+pub struct CancelCurve<'info> {
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve", curve.key().as_ref()],
+        bump = curve.reserve_bump
+    )]
+    /// CHECK: Reserve vault PDA
+    pub reserve_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"fee_vault", curve.key().as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    /// Creator's holding from creator_initial_buy (required once the creator has bought)
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"holder", curve.key().as_ref(), creator.key().as_ref()],
+        bump = key_holder.bump
+    )]
+    pub key_holder: Option<Account<'info, KeyHolder>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: Fee recipient, must match the curve's recorded wallet
+    #[account(mut, address = curve.platform_treasury)]
+    pub platform_treasury: AccountInfo<'info>,
+
+    /// CHECK: Fee recipient, must match the curve's recorded wallet
+    #[account(mut, address = curve.buyback_wallet)]
+    pub buyback_wallet: AccountInfo<'info>,
+
+    /// CHECK: Fee recipient, must match the curve's recorded wallet
+    #[account(mut, address = curve.community_wallet)]
+    pub community_wallet: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BuyKeys<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve", curve.key().as_ref()],
+        bump = curve.reserve_bump
+    )]
+    /// CHECK: Reserve vault PDA
+    pub reserve_vault: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = KeyHolder::LEN,
+        seeds = [b"holder", curve.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault", curve.key().as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    /// Referral code account (binds on first buy, must match the binding afterwards)
+    #[account(mut, has_one = curve)]
+    pub referral_code: Option<Account<'info, Referrer>>,
+
+    /// CHECK: Config PDA (deserialized in instruction)
+    #[account(seeds = [b"config"], bump)]
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: Buyer's BanRecord PDA (banned iff it exists)
+    #[account(seeds = [b"ban", buyer.key().as_ref()], bump)]
+    pub ban_record: UncheckedAccount<'info>,
+
+    /// CHECK: Buyer's BotReport PDA (restricted while flagged)
+    #[account(seeds = [b"bot_report", buyer.key().as_ref()], bump)]
+    pub bot_report: UncheckedAccount<'info>,
+
+    /// CHECK: Curve's CurveAccessList PDA (no restrictions if it does not exist)
+    #[account(seeds = [b"access_list", curve.key().as_ref()], bump)]
+    pub access_list: UncheckedAccount<'info>,
+
+    /// CHECK: Buyer's CurveAccessEntry PDA on this curve (may not exist)
+    #[account(seeds = [b"access", curve.key().as_ref(), buyer.key().as_ref()], bump)]
+    pub access_entry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SellKeys<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve", curve.key().as_ref()],
+        bump = curve.reserve_bump
+    )]
+    /// CHECK: Reserve vault PDA
+    pub reserve_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"holder", curve.key().as_ref(), seller.key().as_ref()],
+        bump = key_holder.bump
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault", curve.key().as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    /// Referral code account (binds on first buy, must match the binding afterwards)
+    #[account(mut, has_one = curve)]
+    pub referral_code: Option<Account<'info, Referrer>>,
+
+    /// CHECK: Seller's BanRecord PDA (banned iff it exists)
+    #[account(seeds = [b"ban", seller.key().as_ref()], bump)]
+    pub ban_record: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, CurveConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Referrer::LEN,
+        seeds = [b"referrer", curve.key().as_ref(), code.as_bytes()],
+        bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Quote<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    /// Optional holder account, used for the remaining per-wallet cap
+    #[account(has_one = curve)]
+    pub key_holder: Option<Account<'info, KeyHolder>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault", curve.key().as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    /// CHECK: Recipient wallet, must match the ledger's address on the curve
+    #[account(mut)]
+    pub recipient_wallet: AccountInfo<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, CurveConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault", curve.key().as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(mut, has_one = curve, has_one = owner)]
+    pub referral_code: Account<'info, Referrer>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, CurveConfig>,
+}
+
+#[derive(Accounts)]
+pub struct CreateAccessList<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump,
+        has_one = creator
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = CurveAccessList::LEN,
+        seeds = [b"access_list", curve.key().as_ref()],
+        bump
+    )]
+    pub access_list: Account<'info, CurveAccessList>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAccessList<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump,
+        has_one = creator
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"access_list", curve.key().as_ref()],
+        bump = access_list.bump
+    )]
+    pub access_list: Account<'info, CurveAccessList>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct SetAccessEntry<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump,
+        has_one = creator
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CurveAccessEntry::LEN,
+        seeds = [b"access", curve.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    pub access_entry: Account<'info, CurveAccessEntry>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAccessEntry<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump,
+        has_one = creator
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"access", curve.key().as_ref(), access_entry.wallet.as_ref()],
+        bump = access_entry.bump
+    )]
+    pub access_entry: Account<'info, CurveAccessEntry>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(reported_account: Pubkey)]
+pub struct ReportBot<'info> {
+    #[account(
+        init_if_needed,
+        payer = reporter,
+        space = BotReport::LEN,
+        seeds = [b"bot_report", reported_account.as_ref()],
+        bump
+    )]
+    pub bot_report: Account<'info, BotReport>,
+
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfirmBotReport<'info> {
+    #[account(seeds = [b"ban_list"], bump = ban_list.bump)]
+    pub ban_list: Account<'info, BanList>,
+
+    #[account(
+        mut,
+        close = rent_receiver,
+        seeds = [b"bot_report", bot_report.reported.as_ref()],
+        bump = bot_report.bump
+    )]
+    pub bot_report: Account<'info, BotReport>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = BanRecord::LEN,
+        seeds = [b"ban", bot_report.reported.as_ref()],
+        bump
+    )]
+    pub ban_record: Account<'info, BanRecord>,
+
+    /// CHECK: First reporter, who paid the report's rent
+    #[account(mut, address = bot_report.reporters[0].reporter)]
+    pub rent_receiver: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+
+    /// Pays rent for the ban record (separate so a multisig signer can be the authority)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DismissBotReport<'info> {
+    #[account(seeds = [b"ban_list"], bump = ban_list.bump)]
+    pub ban_list: Account<'info, BanList>,
+
+    #[account(
+        mut,
+        close = rent_receiver,
+        seeds = [b"bot_report", bot_report.reported.as_ref()],
+        bump = bot_report.bump
+    )]
+    pub bot_report: Account<'info, BotReport>,
+
+    /// CHECK: First reporter, who paid the report's rent
+    #[account(mut, address = bot_report.reporters[0].reporter)]
+    pub rent_receiver: AccountInfo<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, CurveConfig>,
+
+    /// CHECK: Community wallet receiving slashed stakes
+    #[account(mut, address = config.community_wallet)]
+    pub community_wallet: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(account_to_ban: Pubkey)]
+pub struct BanAccount<'info> {
+    #[account(seeds = [b"ban_list"], bump = ban_list.bump)]
+    pub ban_list: Account<'info, BanList>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = BanRecord::LEN,
+        seeds = [b"ban", account_to_ban.as_ref()],
+        bump
+    )]
+    pub ban_record: Account<'info, BanRecord>,
+
+    pub authority: Signer<'info>,
+
+    /// Pays rent for the record (separate so a multisig signer can be the authority)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnbanAccount<'info> {
+    #[account(seeds = [b"ban_list"], bump = ban_list.bump)]
+    pub ban_list: Account<'info, BanList>,
+
+    #[account(
+        mut,
+        close = rent_receiver,
+        seeds = [b"ban", ban_record.wallet.as_ref()],
+        bump = ban_record.bump
+    )]
+    pub ban_record: Account<'info, BanRecord>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Receives the record's rent
+    #[account(mut)]
+    pub rent_receiver: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct MigrateBan<'info> {
+    #[account(mut, seeds = [b"ban_list"], bump = ban_list.bump)]
+    pub ban_list: Account<'info, BanList>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = BanRecord::LEN,
+        seeds = [b"ban", wallet.as_ref()],
+        bump
+    )]
+    pub ban_record: Account<'info, BanRecord>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetBanListAuthority<'info> {
+    #[account(mut, seeds = [b"ban_list"], bump = ban_list.bump)]
+    pub ban_list: Account<'info, BanList>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminAction<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, CurveConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminCurveAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, CurveConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QueueAdminAction<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, CurveConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PendingAdminAction::LEN,
+        seeds = [b"admin_action", config.admin_action_nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pending_action: Account<'info, PendingAdminAction>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveAdminAction<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, CurveConfig>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"admin_action", pending_action.id.to_le_bytes().as_ref()],
+        bump = pending_action.bump
+    )]
+    pub pending_action: Account<'info, PendingAdminAction>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(multisig_id: u64)]
+pub struct CreateMultisig<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = Multisig::LEN,
+        seeds = [b"multisig", creator.key().as_ref(), multisig_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: Multisig signer PDA (no data, signs executed proposals)
+    #[account(seeds = [b"multisig_signer", multisig.key().as_ref()], bump)]
+    pub multisig_signer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMultisigSigners<'info> {
+    #[account(mut)]
+    pub multisig: Account<'info, Multisig>,
+
+    /// Only an executed proposal of this multisig can sign as its signer PDA
+    #[account(
+        seeds = [b"multisig_signer", multisig.key().as_ref()],
+        bump = multisig.signer_bump
+    )]
+    pub multisig_signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey, accounts: Vec<ProposalAccount>, data: Vec<u8>)]
+pub struct ProposeMultisigAction<'info> {
+    #[account(mut)]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: Multisig signer PDA (the only signer a proposal may require)
+    #[account(
+        seeds = [b"multisig_signer", multisig.key().as_ref()],
+        bump = multisig.signer_bump
+    )]
+    pub multisig_signer: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = MultisigProposal::space(accounts.len(), data.len()),
+        seeds = [b"multisig_proposal", multisig.key().as_ref(), multisig.nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveMultisigAction<'info> {
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(mut, has_one = multisig)]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteMultisigAction<'info> {
+    /// Read-only here so a proposal that edits the multisig is not overwritten on exit
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(mut, has_one = multisig)]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    pub executor: Signer<'info>,
+}
+
+// ============================================================================
+// V4 CONTEXT STRUCTS
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct FreezeCurve<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    /// Creator (for manual freeze) or anyone (for auto-freeze)
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: Config in the pre-upgrade layout (cannot deserialize); discriminator checked in handler
+    #[account(mut, seeds = [b"config"], bump, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+
+    /// Pays the extra rent for the larger layout
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCurve<'info> {
+    /// CHECK: Curve in the pre-upgrade layout (cannot deserialize); discriminator checked in handler
+    #[account(mut, owner = crate::ID)]
+    pub curve: UncheckedAccount<'info>,
+
+    /// Pre-upgrade curves paid fees out directly, so the vault starts empty
+    #[account(
+        init,
+        payer = payer,
+        space = FeeVault::LEN,
+        seeds = [b"fee_vault", curve.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Account in a pre-upgrade layout (cannot deserialize); discriminator checked in handler
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    /// Pays any extra rent for the larger layout
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankFreeze<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    /// Pays the keeper reward; omit it to crank without a reward
+    #[account(mut, seeds = [b"keeper_pool"], bump = keeper_pool.bump)]
+    pub keeper_pool: Option<Account<'info, KeeperPool>>,
+
+    /// Anyone can crank; receives the keeper reward
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundKeeperPool<'info> {
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = KeeperPool::LEN,
+        seeds = [b"keeper_pool"],
+        bump
+    )]
+    pub keeper_pool: Account<'info, KeeperPool>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSnapshot<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = Snapshot::LEN,
+        seeds = [b"snapshot", curve.key().as_ref()],
+        bump
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    /// CHECK: On-chain snapshot builder PDA, must not exist
+    #[account(seeds = [b"snapshot_builder", curve.key().as_ref()], bump)]
+    pub builder: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BeginSnapshot<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = SnapshotBuilder::LEN,
+        seeds = [b"snapshot_builder", curve.key().as_ref()],
+        bump
+    )]
+    pub builder: Account<'info, SnapshotBuilder>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterSnapshotHolder<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        has_one = curve,
+        seeds = [b"snapshot_builder", curve.key().as_ref()],
+        bump = builder.bump
+    )]
+    pub builder: Account<'info, SnapshotBuilder>,
+
+    #[account(
+        seeds = [b"holder", curve.key().as_ref(), key_holder.owner.as_ref()],
+        bump = key_holder.bump
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+
+    /// CHECK: Snapshot PDA address (created by finalize_snapshot), only used as a seed
+    #[account(seeds = [b"snapshot", curve.key().as_ref()], bump)]
+    pub snapshot: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ClaimRecord::LEN,
+        seeds = [b"claim", snapshot.key().as_ref(), key_holder.owner.as_ref()],
+        bump
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeSnapshot<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        has_one = curve,
+        close = builder_payer,
+        seeds = [b"snapshot_builder", curve.key().as_ref()],
+        bump = builder.bump
+    )]
+    pub builder: Account<'info, SnapshotBuilder>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Snapshot::LEN,
+        seeds = [b"snapshot", curve.key().as_ref()],
+        bump
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    /// CHECK: Receives the builder's rent, must be whoever paid for it
+    #[account(mut, address = builder.payer)]
+    pub builder_payer: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSnapshotBuilder<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        has_one = curve,
+        close = builder_payer,
+        seeds = [b"snapshot_builder", curve.key().as_ref()],
+        bump = builder.bump
+    )]
+    pub builder: Account<'info, SnapshotBuilder>,
+
+    /// CHECK: Receives the builder's rent, must be whoever paid for it
+    #[account(mut, address = builder.payer)]
+    pub builder_payer: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct LaunchToken<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve", curve.key().as_ref()],
+        bump = curve.reserve_bump
+    )]
+    /// CHECK: Reserve vault PDA
+    pub reserve_vault: AccountInfo<'info>,
 
-        // Transfer tokens from airdrop vault to holder
-        // (This will be implemented with actual SPL token transfer once we have the mint)
-        // For now, we just record the claim
+    /// CHECK: LP vault to receive SOL for initial Pump.fun buy
+    #[account(mut)]
+    pub lp_vault: AccountInfo<'info>,
 
-        // Record claim
-        claim_record.snapshot = snapshot.key();
-        claim_record.holder = holder.owner;
-        claim_record.amount_claimed = token_amount;
-        claim_record.claimed_at = clock.unix_timestamp;
-        claim_record.bump = ctx.bumps.claim_record;
+    /// CHECK: Marketing wallet
+    #[account(mut)]
+    pub marketing_wallet: AccountInfo<'info>,
 
-        // Emit event
-        emit!(TokensClaimedEvent {
-            snapshot: snapshot.key(),
-            holder: holder.owner,
-            keys_held: holder.amount,
-            tokens_claimed: token_amount,
-            timestamp: clock.unix_timestamp,
-        });
+    /// CHECK: Utility wallet
+    #[account(mut)]
+    pub utility_wallet: AccountInfo<'info>,
 
-        msg!("✅ Tokens claimed: {}", token_amount);
-        msg!("Keys held: {}", holder.amount);
-        Ok(())
-    }
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, CurveConfig>,
+
+    #[account(
+        has_one = curve,
+        seeds = [b"snapshot", curve.key().as_ref()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    pub system_program: Program<'info, System>,
 }
 
-// ============================================================================
-// HELPER FUNCTIONS
-// ============================================================================
+#[derive(Accounts)]
+pub struct LaunchWithMint<'info> {
+    #[account(
+        mut,
+        has_one = creator,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Box<Account<'info, BondingCurve>>,
 
-/// Verify Merkle proof
-fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: &[u8; 32]) -> bool {
-    let mut computed_hash = *leaf;
+    #[account(
+        mut,
+        seeds = [b"reserve", curve.key().as_ref()],
+        bump = curve.reserve_bump
+    )]
+    /// CHECK: Reserve vault PDA
+    pub reserve_vault: AccountInfo<'info>,
 
-    for proof_element in proof.iter() {
-        computed_hash = if computed_hash <= *proof_element {
-            anchor_lang::solana_program::keccak::hashv(&[
-                &computed_hash,
-                proof_element,
-            ]).0
-        } else {
-            anchor_lang::solana_program::keccak::hashv(&[
-                proof_element,
-                &computed_hash,
-            ]).0
-        };
-    }
+    /// CHECK: Program-owned LP vault PDA holding the initial-buy SOL for the pool
+    #[account(
+        init,
+        payer = creator,
+        space = 8,
+        seeds = [b"lp_vault", curve.key().as_ref()],
+        bump
+    )]
+    pub lp_vault: AccountInfo<'info>,
 
-    computed_hash == *root
+    /// CHECK: Marketing wallet
+    #[account(mut)]
+    pub marketing_wallet: AccountInfo<'info>,
+
+    /// CHECK: Utility wallet
+    #[account(mut)]
+    pub utility_wallet: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, CurveConfig>>,
+
+    #[account(
+        mut,
+        has_one = curve,
+        seeds = [b"snapshot", curve.key().as_ref()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Box<Account<'info, Snapshot>>,
+
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = TOKEN_DECIMALS,
+        mint::authority = curve,
+        seeds = [b"mint", curve.key().as_ref()],
+        bump
+    )]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = TokenMetadata::LEN,
+        seeds = [b"metadata", token_mint.key().as_ref()],
+        bump
+    )]
+    pub metadata: Box<Account<'info, TokenMetadata>>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = token_mint,
+        token::authority = snapshot,
+        seeds = [b"airdrop_vault", snapshot.key().as_ref()],
+        bump
+    )]
+    pub airdrop_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = token_mint,
+        token::authority = curve,
+        seeds = [b"liquidity_vault", curve.key().as_ref()],
+        bump
+    )]
+    pub liquidity_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
 }
 
-// ============================================================================
-// CONTEXT STRUCTS
-// ============================================================================
+#[derive(Accounts)]
+#[instruction(holder: Pubkey)]
+pub struct ChallengeSnapshotAmount<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        has_one = curve,
+        seeds = [b"snapshot", curve.key().as_ref()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    /// CHECK: Holder's KeyHolder PDA (0 keys if it does not exist)
+    #[account(seeds = [b"holder", curve.key().as_ref(), holder.as_ref()], bump)]
+    pub key_holder: UncheckedAccount<'info>,
+
+    /// Receives the invalidated snapshot's rent
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+}
 
 #[derive(Accounts)]
-pub struct Initialize<'info> {
+pub struct DisputeSnapshotMembership<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        has_one = curve,
+        seeds = [b"snapshot", curve.key().as_ref()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    #[account(
+        seeds = [b"holder", curve.key().as_ref(), key_holder.owner.as_ref()],
+        bump = key_holder.bump
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+
     #[account(
         init,
-        payer = authority,
-        space = CurveConfig::LEN,
-        seeds = [b"config"],
+        payer = challenger,
+        space = SnapshotDispute::LEN,
+        seeds = [b"dispute", curve.key().as_ref(), key_holder.owner.as_ref()],
         bump
     )]
-    pub config: Account<'info, CurveConfig>,
+    pub dispute: Account<'info, SnapshotDispute>,
 
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub challenger: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeBanList<'info> {
+pub struct AnswerSnapshotDispute<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        has_one = curve,
+        seeds = [b"snapshot", curve.key().as_ref()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    #[account(
+        seeds = [b"holder", curve.key().as_ref(), dispute.holder.as_ref()],
+        bump = key_holder.bump
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+
+    #[account(
+        mut,
+        has_one = curve,
+        has_one = challenger,
+        close = challenger,
+        seeds = [b"dispute", curve.key().as_ref(), dispute.holder.as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, SnapshotDispute>,
+
+    /// CHECK: Dispute opener, receives the dispute's rent
+    #[account(mut)]
+    pub challenger: AccountInfo<'info>,
+
+    /// CHECK: Curve creator, receives the forfeited stake
+    #[account(mut, address = curve.creator)]
+    pub creator: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpholdSnapshotDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    /// CHECK: Snapshot PDA, may already be closed; deserialized in the handler if present
+    #[account(mut, seeds = [b"snapshot", curve.key().as_ref()], bump)]
+    pub snapshot: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = curve,
+        has_one = challenger,
+        close = challenger,
+        seeds = [b"dispute", curve.key().as_ref(), dispute.holder.as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, SnapshotDispute>,
+
+    /// CHECK: Dispute opener, receives the stake, dispute rent and (if upheld) snapshot rent
+    #[account(mut)]
+    pub challenger: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StartRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, CurveConfig>,
+
+    /// Admin (any time) or anyone (after the refund delay)
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve", curve.key().as_ref()],
+        bump = curve.reserve_bump
+    )]
+    /// CHECK: Reserve vault PDA
+    pub reserve_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = holder,
+        seeds = [b"holder", curve.key().as_ref(), holder.key().as_ref()],
+        bump = key_holder.bump
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, CurveConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTokens<'info> {
+    #[account(
+        seeds = [b"snapshot", snapshot.curve.as_ref()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    #[account(
+        seeds = [b"holder", key_holder.curve.as_ref(), key_holder.owner.as_ref()],
+        bump = key_holder.bump,
+        constraint = key_holder.curve == snapshot.curve @ CurveError::Unauthorized
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+
+    /// Already exists (unclaimed) if the holder was registered into an on-chain snapshot
     #[account(
-        init,
-        payer = authority,
-        space = BanList::LEN,
-        seeds = [b"ban_list"],
+        init_if_needed,
+        payer = claimer,
+        space = ClaimRecord::LEN,
+        seeds = [b"claim", snapshot.key().as_ref(), key_holder.owner.as_ref()],
         bump
     )]
-    pub ban_list: Account<'info, BanList>,
-
-    #[account(mut)]
-    pub authority: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    pub claim_record: Account<'info, ClaimRecord>,
 
-#[derive(Accounts)]
-#[instruction(twitter_handle: String)]
-pub struct CreateCurve<'info> {
     #[account(
-        init,
-        payer = creator,
-        space = BondingCurve::LEN,
-        seeds = [b"curve", twitter_handle.as_bytes()],
-        bump
+        mut,
+        seeds = [b"airdrop_vault", snapshot.key().as_ref()],
+        bump = snapshot.vault_bump
     )]
-    pub curve: Account<'info, BondingCurve>,
+    pub airdrop_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = airdrop_vault.mint)]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Holder wallet, owner of the receiving token account
+    #[account(address = key_holder.owner)]
+    pub holder: UncheckedAccount<'info>,
 
     #[account(
-        init,
-        payer = creator,
-        space = 8 + 0, // Empty PDA for reserve vault
-        seeds = [b"reserve", curve.key().as_ref()],
-        bump
+        init_if_needed,
+        payer = claimer,
+        associated_token::mint = token_mint,
+        associated_token::authority = holder
     )]
-    /// CHECK: Reserve vault PDA
-    pub reserve_vault: AccountInfo<'info>,
+    pub holder_token_account: Box<Account<'info, TokenAccount>>,
 
+    /// Pays for the claim record and, if needed, the holder's token account
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub claimer: Signer<'info>,
 
-    #[account(seeds = [b"config"], bump)]
+    #[account(seeds = [b"config"], bump = config.bump)]
     pub config: Account<'info, CurveConfig>,
 
-    /// CHECK: Ban list validation is optional for now (devnet compatibility)
-    #[account(seeds = [b"ban_list"], bump)]
-    pub ban_list: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct CreatorInitialBuy<'info> {
+pub struct SetTokenMint<'info> {
     #[account(
         mut,
+        has_one = creator,
         seeds = [b"curve", curve.twitter_handle.as_bytes()],
         bump = curve.bump
     )]
@@ -1210,292 +4915,295 @@ pub struct CreatorInitialBuy<'info> {
 
     #[account(
         mut,
-        seeds = [b"reserve", curve.key().as_ref()],
-        bump = curve.reserve_bump
+        has_one = curve,
+        seeds = [b"snapshot", curve.key().as_ref()],
+        bump = snapshot.bump
     )]
-    /// CHECK: Reserve vault PDA
-    pub reserve_vault: AccountInfo<'info>,
+    pub snapshot: Account<'info, Snapshot>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
 
     #[account(
         init,
-        payer = buyer,
-        space = KeyHolder::LEN,
-        seeds = [b"holder", curve.key().as_ref(), buyer.key().as_ref()],
+        payer = creator,
+        token::mint = token_mint,
+        token::authority = snapshot,
+        seeds = [b"airdrop_vault", snapshot.key().as_ref()],
         bump
     )]
-    pub key_holder: Account<'info, KeyHolder>,
+    pub airdrop_vault: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
-    pub buyer: Signer<'info>,
-
-    /// CHECK: Creator wallet for fee distribution
-    #[account(mut, address = curve.creator)]
-    pub creator: AccountInfo<'info>,
-
-    /// CHECK: Platform treasury for fee distribution
-    #[account(mut, address = curve.platform_treasury)]
-    pub platform_treasury: AccountInfo<'info>,
-
-    /// CHECK: Buyback/burn wallet
-    #[account(mut, address = curve.buyback_wallet)]
-    pub buyback_wallet: AccountInfo<'info>,
-
-    /// CHECK: Community rewards wallet
-    #[account(mut, address = curve.community_wallet)]
-    pub community_wallet: AccountInfo<'info>,
+    pub creator: Signer<'info>,
 
-    #[account(seeds = [b"config"], bump)]
-    pub config: Account<'info, CurveConfig>,
+    pub token_program: Program<'info, Token>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ActivateCurve<'info> {
+pub struct FundAirdrop<'info> {
     #[account(
-        mut,
+        has_one = creator,
         seeds = [b"curve", curve.twitter_handle.as_bytes()],
         bump = curve.bump
     )]
     pub curve: Account<'info, BondingCurve>,
 
-    #[account(mut, address = curve.creator)]
+    #[account(
+        mut,
+        has_one = curve,
+        seeds = [b"snapshot", curve.key().as_ref()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_vault", snapshot.key().as_ref()],
+        bump = snapshot.vault_bump
+    )]
+    pub airdrop_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = airdrop_vault.mint)]
+    pub creator_token_account: Box<Account<'info, TokenAccount>>,
+
     pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct BuyKeys<'info> {
+pub struct CreatePool<'info> {
     #[account(
-        mut,
         seeds = [b"curve", curve.twitter_handle.as_bytes()],
         bump = curve.bump
     )]
-    pub curve: Account<'info, BondingCurve>,
+    pub curve: Box<Account<'info, BondingCurve>>,
 
     #[account(
-        mut,
-        seeds = [b"reserve", curve.key().as_ref()],
-        bump = curve.reserve_bump
+        init,
+        payer = payer,
+        space = LiquidityPool::LEN,
+        seeds = [b"pool", curve.key().as_ref()],
+        bump
     )]
-    /// CHECK: Reserve vault PDA
-    pub reserve_vault: AccountInfo<'info>,
+    pub pool: Box<Account<'info, LiquidityPool>>,
 
+    /// CHECK: Program-owned lp_vault PDA created by launch_with_mint
     #[account(
-        init_if_needed,
-        payer = buyer,
-        space = KeyHolder::LEN,
-        seeds = [b"holder", curve.key().as_ref(), buyer.key().as_ref()],
-        bump
+        seeds = [b"lp_vault", curve.key().as_ref()],
+        bump,
+        owner = crate::ID
     )]
-    pub key_holder: Account<'info, KeyHolder>,
-
-    #[account(mut)]
-    pub buyer: Signer<'info>,
-
-    /// CHECK: Creator wallet for fee distribution
-    #[account(mut, address = curve.creator)]
-    pub creator: AccountInfo<'info>,
+    pub lp_vault: UncheckedAccount<'info>,
 
-    /// CHECK: Platform treasury for fee distribution
-    #[account(mut, address = curve.platform_treasury)]
-    pub platform_treasury: AccountInfo<'info>,
+    #[account(constraint = curve.token_mint == Some(token_mint.key()) @ CurveError::InsufficientLiquidity)]
+    pub token_mint: Box<Account<'info, Mint>>,
 
-    /// CHECK: Buyback/burn wallet
-    #[account(mut, address = curve.buyback_wallet)]
-    pub buyback_wallet: AccountInfo<'info>,
+    #[account(
+        token::mint = token_mint,
+        token::authority = curve,
+        seeds = [b"liquidity_vault", curve.key().as_ref()],
+        bump
+    )]
+    pub liquidity_vault: Box<Account<'info, TokenAccount>>,
 
-    /// CHECK: Community rewards wallet
-    #[account(mut, address = curve.community_wallet)]
-    pub community_wallet: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = TOKEN_DECIMALS,
+        mint::authority = pool,
+        seeds = [b"lp_mint", pool.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
 
-    /// CHECK: Optional referrer for fee distribution
     #[account(mut)]
-    pub referrer: Option<AccountInfo<'info>>,
-
-    /// CHECK: Config account (validation done in instruction)
-    pub config: UncheckedAccount<'info>,
+    pub payer: Signer<'info>,
 
-    /// CHECK: Ban list account (validation done in instruction)
-    pub ban_list: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SellKeys<'info> {
+pub struct PoolSwap<'info> {
     #[account(
-        mut,
         seeds = [b"curve", curve.twitter_handle.as_bytes()],
         bump = curve.bump
     )]
-    pub curve: Account<'info, BondingCurve>,
+    pub curve: Box<Account<'info, BondingCurve>>,
 
     #[account(
         mut,
-        seeds = [b"reserve", curve.key().as_ref()],
-        bump = curve.reserve_bump
+        has_one = curve,
+        seeds = [b"pool", curve.key().as_ref()],
+        bump = pool.bump
     )]
-    /// CHECK: Reserve vault PDA
-    pub reserve_vault: AccountInfo<'info>,
+    pub pool: Box<Account<'info, LiquidityPool>>,
 
+    /// CHECK: Pool SOL vault (program-owned lp_vault PDA)
     #[account(
         mut,
-        seeds = [b"holder", curve.key().as_ref(), seller.key().as_ref()],
-        bump = key_holder.bump
+        seeds = [b"lp_vault", curve.key().as_ref()],
+        bump = pool.sol_vault_bump
     )]
-    pub key_holder: Account<'info, KeyHolder>,
-
-    #[account(mut)]
-    pub seller: Signer<'info>,
+    pub lp_vault: UncheckedAccount<'info>,
 
-    /// CHECK: Creator wallet for fee distribution
-    #[account(mut, address = curve.creator)]
-    pub creator: AccountInfo<'info>,
-
-    /// CHECK: Platform treasury
-    #[account(mut, address = curve.platform_treasury)]
-    pub platform_treasury: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"liquidity_vault", curve.key().as_ref()],
+        bump = pool.token_vault_bump
+    )]
+    pub liquidity_vault: Box<Account<'info, TokenAccount>>,
 
-    /// CHECK: Buyback/burn wallet
-    #[account(mut, address = curve.buyback_wallet)]
-    pub buyback_wallet: AccountInfo<'info>,
+    #[account(address = pool.token_mint)]
+    pub token_mint: Box<Account<'info, Mint>>,
 
-    /// CHECK: Community rewards wallet
-    #[account(mut, address = curve.community_wallet)]
-    pub community_wallet: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
 
-    /// CHECK: Optional referrer for fee distribution
     #[account(mut)]
-    pub referrer: Option<AccountInfo<'info>>,
-
-    #[account(seeds = [b"ban_list"], bump)]
-    pub ban_list: Account<'info, BanList>,
+    pub user: Signer<'info>,
 
-    pub system_program: Program<'info, System>,
-}
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, CurveConfig>>,
 
-#[derive(Accounts)]
-pub struct ReportBot<'info> {
-    pub reporter: Signer<'info>,
-}
+    pub token_program: Program<'info, Token>,
 
-#[derive(Accounts)]
-pub struct BanAccount<'info> {
-    #[account(mut, seeds = [b"ban_list"], bump = ban_list.bump)]
-    pub ban_list: Account<'info, BanList>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 
-    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct AdminAction<'info> {
-    #[account(mut, seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, CurveConfig>,
+pub struct AddLiquidity<'info> {
+    #[account(
+        seeds = [b"curve", curve.twitter_handle.as_bytes()],
+        bump = curve.bump
+    )]
+    pub curve: Box<Account<'info, BondingCurve>>,
 
-    pub authority: Signer<'info>,
-}
+    #[account(
+        mut,
+        has_one = curve,
+        has_one = lp_mint,
+        seeds = [b"pool", curve.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
 
-// ============================================================================
-// V4 CONTEXT STRUCTS
-// ============================================================================
+    /// CHECK: Pool SOL vault (program-owned lp_vault PDA)
+    #[account(
+        mut,
+        seeds = [b"lp_vault", curve.key().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub lp_vault: UncheckedAccount<'info>,
 
-#[derive(Accounts)]
-pub struct FreezeCurve<'info> {
     #[account(
         mut,
-        seeds = [b"curve", curve.twitter_handle.as_bytes()],
-        bump = curve.bump
+        seeds = [b"liquidity_vault", curve.key().as_ref()],
+        bump = pool.token_vault_bump
     )]
-    pub curve: Account<'info, BondingCurve>,
+    pub liquidity_vault: Box<Account<'info, TokenAccount>>,
 
-    /// Creator (for manual freeze) or anyone (for auto-freeze)
-    pub creator: Signer<'info>,
-}
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, Mint>>,
 
-#[derive(Accounts)]
-pub struct CreateSnapshot<'info> {
     #[account(
         mut,
-        seeds = [b"curve", curve.twitter_handle.as_bytes()],
-        bump = curve.bump
+        token::mint = pool.token_mint,
+        token::authority = user
     )]
-    pub curve: Account<'info, BondingCurve>,
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
 
     #[account(
-        init,
-        payer = creator,
-        space = Snapshot::LEN,
-        seeds = [b"snapshot", curve.key().as_ref()],
-        bump
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_mint,
+        associated_token::authority = user
     )]
-    pub snapshot: Account<'info, Snapshot>,
+    pub user_lp_account: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub user: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, CurveConfig>>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct LaunchToken<'info> {
+pub struct RemoveLiquidity<'info> {
     #[account(
-        mut,
         seeds = [b"curve", curve.twitter_handle.as_bytes()],
         bump = curve.bump
     )]
-    pub curve: Account<'info, BondingCurve>,
+    pub curve: Box<Account<'info, BondingCurve>>,
 
     #[account(
         mut,
-        seeds = [b"reserve", curve.key().as_ref()],
-        bump = curve.reserve_bump
+        has_one = curve,
+        has_one = lp_mint,
+        seeds = [b"pool", curve.key().as_ref()],
+        bump = pool.bump
     )]
-    /// CHECK: Reserve vault PDA
-    pub reserve_vault: AccountInfo<'info>,
-
-    /// CHECK: LP vault to receive SOL for initial Pump.fun buy
-    #[account(mut)]
-    pub lp_vault: AccountInfo<'info>,
+    pub pool: Box<Account<'info, LiquidityPool>>,
 
-    /// CHECK: Marketing wallet
-    #[account(mut)]
-    pub marketing_wallet: AccountInfo<'info>,
+    /// CHECK: Pool SOL vault (program-owned lp_vault PDA)
+    #[account(
+        mut,
+        seeds = [b"lp_vault", curve.key().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub lp_vault: UncheckedAccount<'info>,
 
-    /// CHECK: Utility wallet
-    #[account(mut)]
-    pub utility_wallet: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"liquidity_vault", curve.key().as_ref()],
+        bump = pool.token_vault_bump
+    )]
+    pub liquidity_vault: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub lp_mint: Box<Account<'info, Mint>>,
 
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct ClaimTokens<'info> {
     #[account(
-        seeds = [b"snapshot", snapshot.curve.as_ref()],
-        bump = snapshot.bump
+        mut,
+        token::mint = lp_mint,
+        token::authority = user
     )]
-    pub snapshot: Account<'info, Snapshot>,
+    pub user_lp_account: Box<Account<'info, TokenAccount>>,
 
-    #[account(
-        seeds = [b"holder", key_holder.curve.as_ref(), key_holder.owner.as_ref()],
-        bump = key_holder.bump
-    )]
-    pub key_holder: Account<'info, KeyHolder>,
+    #[account(address = pool.token_mint)]
+    pub token_mint: Box<Account<'info, Mint>>,
 
     #[account(
-        init,
-        payer = claimer,
-        space = ClaimRecord::LEN,
-        seeds = [b"claim", snapshot.key().as_ref(), key_holder.owner.as_ref()],
-        bump
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = user
     )]
-    pub claim_record: Account<'info, ClaimRecord>,
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
-    pub claimer: Signer<'info>,
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
 
     pub system_program: Program<'info, System>,
 }
@@ -1,20 +1,25 @@
+//! Bonding curve math module - V6
+//! Formula: P(S) = 0.05 + 0.0003*S + 0.0000012*S^1.6
+//! Where:
+//!   - P(S) = price at supply S (in SOL)
+//!   - S = current supply
+//!   - Base: 0.05 SOL (50,000,000 lamports)
+//!   - Linear: 0.0003 SOL per key (300,000 lamports)
+//!   - Exponential: 0.0000012 * S^1.6 (1,200 lamports coefficient)
+
 use anchor_lang::prelude::*;
 use crate::errors::CurveError;
-
-/// Bonding curve math module - V6
-/// Formula: P(S) = 0.05 + 0.0003*S + 0.0000012*S^1.6
-/// Where:
-///   - P(S) = price at supply S (in SOL)
-///   - S = current supply
-///   - Base: 0.05 SOL (50,000,000 lamports)
-///   - Linear: 0.0003 SOL per key (300,000 lamports)
-///   - Exponential: 0.0000012 * S^1.6 (1,200 lamports coefficient)
+use crate::fixed_point;
+use crate::state::FeeSchedule;
 
 /// Constants for the hybrid exponential formula
 pub const BASE_PRICE_LAMPORTS: u128 = 50_000_000; // 0.05 SOL
 pub const LINEAR_COEFFICIENT: u128 = 300_000;      // 0.0003 SOL per key
 pub const EXPONENTIAL_COEFFICIENT: u128 = 1_200;   // 0.0000012 SOL scaled
 
+/// Fixed-point scale of S^0.6 values (S^0.6 is carried as S^0.6 * POWER_SCALE)
+pub const POWER_SCALE: u128 = fixed_point::SCALE;
+
 /// Calculate price at a specific supply level
 /// P(S) = 0.05 + 0.0003*S + 0.0000012*S^1.6
 pub fn calculate_price_at_supply(supply: u128) -> Result<u128> {
@@ -27,15 +32,15 @@ pub fn calculate_price_at_supply(supply: u128) -> Result<u128> {
         .ok_or(error!(CurveError::ArithmeticOverflow))?;
 
     // Exponential component: 0.0000012 * S^1.6
-    // S^1.6 = S * S^0.6
-    let s_to_0_6 = approximate_power_0_6(supply)?;
+    // S^1.6 = S * S^0.6 (S^0.6 in fixed point, within 1e-9 of exact)
+    let s_to_0_6 = fixed_point::pow_0_6(supply)?;
 
     let exponential_term = supply
         .checked_mul(s_to_0_6)
         .ok_or(error!(CurveError::ArithmeticOverflow))?
         .checked_mul(EXPONENTIAL_COEFFICIENT)
         .ok_or(error!(CurveError::ArithmeticOverflow))?
-        .checked_div(1_000_000_000) // Scale down
+        .checked_div(POWER_SCALE) // Scale down
         .ok_or(error!(CurveError::ArithmeticOverflow))?;
 
     // Total price = base + linear + exponential
@@ -48,143 +53,124 @@ pub fn calculate_price_at_supply(supply: u128) -> Result<u128> {
     Ok(total_price)
 }
 
-/// Approximate S^0.6 using a simplified power calculation
-/// For production, this uses a lookup table for common values
-/// and linear interpolation for values in between
-pub fn approximate_power_0_6(supply: u128) -> Result<u128> {
-    if supply == 0 {
+/// Calculate total cost to buy `amount` keys starting from `current_supply`
+///
+/// Closed form of Σ P(k) for k in [current_supply, current_supply + amount),
+/// so compute cost is the same for any purchase size:
+///   - Base:        base * n
+///   - Linear:      c1 * (n * S0 + n * (n - 1) / 2)            (exact)
+///   - Exponential: c2 * Σ k^1.6, via Euler-Maclaurin on k^1.6:
+///     [S^2.6 / 2.6 - S^1.6 / 2 + 1.6 * S^0.6 / 12] from S0 to S0 + n
+///     = [S^0.6 * (150*S^2 - 195*S + 52) / 390] from S0 to S0 + n
+///
+/// The per-key sum floors every price to whole lamports, the closed form floors
+/// once. The two agree within `amount + cost / 1_000_000` lamports (1 lamport per
+/// key plus 1 ppm), checked against the per-key reference sum in tests.
+pub fn calculate_buy_cost(current_supply: u128, amount: u64) -> Result<u128> {
+    if amount == 0 {
         return Ok(0);
     }
 
-    // For small values, use lookup table
-    if supply <= 1000 {
-        return Ok(lookup_power_0_6(supply));
-    }
-
-    // For larger values, use approximation: x^0.6 ≈ x^(3/5)
-    // We use Newton's method approximation
-    // x^0.6 ≈ x * (1 - 0.4 * ln(x) + 0.08 * ln(x)^2) for moderate x
+    let n = amount as u128;
+    let end_supply = current_supply
+        .checked_add(n)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?;
 
-    // Simplified: Use a good enough approximation
-    // x^0.6 ≈ sqrt(x) * cbrt(x)^(1/5) ≈ practical approximation
+    // Base component: base * n
+    let base_sum = BASE_PRICE_LAMPORTS
+        .checked_mul(n)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?;
 
-    // For efficiency, we use: x^0.6 ≈ x^(2/3) * 0.9
-    // This is within 5% accuracy for most values
-    let approx = approximate_power_2_3(supply)?
-        .checked_mul(9)
+    // Linear component: c1 * (n * S0 + n * (n - 1) / 2)
+    let supply_sum = n
+        .checked_mul(current_supply)
         .ok_or(error!(CurveError::ArithmeticOverflow))?
-        .checked_div(10)
+        .checked_add(n * (n - 1) / 2)
         .ok_or(error!(CurveError::ArithmeticOverflow))?;
 
-    Ok(approx)
-}
-
-/// Approximate x^(2/3) using integer math
-fn approximate_power_2_3(x: u128) -> Result<u128> {
-    if x == 0 {
-        return Ok(0);
-    }
+    let linear_sum = supply_sum
+        .checked_mul(LINEAR_COEFFICIENT)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?;
 
-    // x^(2/3) = (x^2)^(1/3) = cbrt(x^2)
-    let x_squared = x.checked_mul(x).ok_or(error!(CurveError::ArithmeticOverflow))?;
+    // Exponential component: c2 * (G(S1) - G(S0)) / (390 * POWER_SCALE)
+    let exponential_sum = exponential_antiderivative(end_supply)?
+        .checked_sub(exponential_antiderivative(current_supply)?)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?
+        .checked_mul(EXPONENTIAL_COEFFICIENT)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?
+        .checked_div(390 * POWER_SCALE)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?;
 
-    // Integer cube root
-    let result = integer_cbrt(x_squared);
+    let total_cost = base_sum
+        .checked_add(linear_sum)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?
+        .checked_add(exponential_sum)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?;
 
-    Ok(result)
+    Ok(total_cost)
 }
 
-/// Integer cube root using binary search
-fn integer_cbrt(n: u128) -> u128 {
-    if n == 0 {
-        return 0;
-    }
-    if n == 1 {
-        return 1;
-    }
-
-    let mut low = 0u128;
-    let mut high = n;
-    let mut result = 0u128;
-
-    while low <= high {
-        let mid = low + (high - low) / 2;
-        let cube = mid.saturating_mul(mid).saturating_mul(mid);
-
-        if cube == n {
-            return mid;
-        } else if cube < n {
-            result = mid;
-            low = mid + 1;
+/// Largest key count whose total buy cost fits within `budget` lamports
+/// Binary search over `calculate_buy_cost` (each probe is constant cost),
+/// bounded by `max_amount` (purchase limit / remaining wallet cap)
+pub fn calculate_keys_for_budget(
+    current_supply: u128,
+    budget: u128,
+    max_amount: u64,
+) -> Result<u64> {
+    let mut low = 0u64;
+    let mut high = max_amount;
+
+    // Invariant: cost(low) <= budget; answer lies in [low, high]
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+
+        if calculate_buy_cost(current_supply, mid)? <= budget {
+            low = mid;
         } else {
-            if mid == 0 {
-                break;
-            }
             high = mid - 1;
         }
     }
 
-    result
-}
-
-/// Lookup table for S^0.6 for small values (0-1000)
-fn lookup_power_0_6(supply: u128) -> u128 {
-    // Pre-computed values for efficiency
-    // These are S^0.6 * 1000 (scaled for precision)
-    match supply {
-        0 => 0,
-        1..=10 => supply * 1000 / 1585,  // Approximate for very small values
-        11..=50 => {
-            // Linear interpolation in this range
-            let base = 10u128.pow(600u32) / 1000;
-            base + (supply - 10) * 200
-        },
-        51..=100 => {
-            let base = 50u128.pow(600u32) / 1000;
-            base + (supply - 50) * 150
-        },
-        101..=500 => {
-            let base = 100u128.pow(600u32) / 1000;
-            base + (supply - 100) * 100
-        },
-        501..=1000 => {
-            let base = 500u128.pow(600u32) / 1000;
-            base + (supply - 500) * 80
-        },
-        _ => {
-            // Fallback to approximation
-            supply * 630 / 100 // Rough approximation
-        }
-    }
+    Ok(low)
 }
 
-/// Calculate total cost to buy `amount` keys starting from `current_supply`
-pub fn calculate_buy_cost(current_supply: u128, amount: u64) -> Result<u128> {
-    if amount == 0 {
+/// G(S) = S^0.6 * (150*S^2 - 195*S + 52), scaled by POWER_SCALE
+/// 390 * Σ k^1.6 ≈ G(S1) - G(S0) (Euler-Maclaurin, see `calculate_buy_cost`)
+/// G is non-decreasing on integers, so G(S1) - G(S0) never underflows
+fn exponential_antiderivative(supply: u128) -> Result<u128> {
+    if supply == 0 {
         return Ok(0);
     }
 
-    let mut total_cost = 0u128;
-
-    // Sum prices for each key from current_supply to (current_supply + amount)
-    for i in 0..amount {
-        let supply_at_i = current_supply
-            .checked_add(i as u128)
-            .ok_or(error!(CurveError::ArithmeticOverflow))?;
+    let s_to_0_6 = fixed_point::pow_0_6(supply)?;
 
-        let price = calculate_price_at_supply(supply_at_i)?;
+    // 150*S^2 + 52 - 195*S (>= 7 for every S >= 1)
+    let polynomial = supply
+        .checked_mul(supply)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?
+        .checked_mul(150)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?
+        .checked_add(52)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?
+        .checked_sub(supply * 195)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?;
 
-        total_cost = total_cost
-            .checked_add(price)
-            .ok_or(error!(CurveError::ArithmeticOverflow))?;
-    }
+    let result = s_to_0_6
+        .checked_mul(polynomial)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?;
 
-    Ok(total_cost)
+    Ok(result)
 }
 
 /// Calculate return amount when selling `amount` keys from `current_supply`
-/// Applies unified 6% fee (94% returned to seller)
-pub fn calculate_sell_return(current_supply: u128, amount: u64) -> Result<u128> {
+/// Applies the schedule's unified fee (reserve_bps returned to seller, 94% by default)
+/// Selling walks back down the same keys, so gross = buy cost from (S - n)
+pub fn calculate_sell_return(
+    current_supply: u128,
+    amount: u64,
+    schedule: &FeeSchedule,
+) -> Result<u128> {
     if amount == 0 {
         return Ok(0);
     }
@@ -194,33 +180,77 @@ pub fn calculate_sell_return(current_supply: u128, amount: u64) -> Result<u128>
         CurveError::InsufficientReserve
     );
 
-    let mut gross_return = 0u128;
+    let gross_return = calculate_buy_cost(current_supply - amount as u128, amount)?;
 
-    // Sum prices for each key being sold (in reverse order)
-    for i in 0..amount {
-        let supply_at_i = current_supply
-            .checked_sub((i + 1) as u128)
-            .ok_or(error!(CurveError::ArithmeticOverflow))?;
+    // Apply fee (user gets reserve_bps share)
+    apply_bps(gross_return, schedule.reserve_bps)
+}
 
-        let price = calculate_price_at_supply(supply_at_i)?;
+/// amount * bps / 10000 (floored)
+fn apply_bps(amount: u128, bps: u16) -> Result<u128> {
+    amount
+        .checked_mul(bps as u128)
+        .ok_or(error!(CurveError::ArithmeticOverflow))?
+        .checked_div(crate::BPS_DENOMINATOR)
+        .ok_or(error!(CurveError::ArithmeticOverflow))
+}
 
-        gross_return = gross_return
-            .checked_add(price)
-            .ok_or(error!(CurveError::ArithmeticOverflow))?;
-    }
+/// Split the 5% fee buckets by referrer type (referral, project, community)
+/// - User:    referrer gets referral_bps, project and community keep their base
+/// - Project: referral bucket folds into project (3% + 1% = 4%)
+/// - None:    unreferred_project_bps of it to project, rest to community (2% / 3%)
+fn route_fee_buckets(
+    amount: u128,
+    referrer_type: ReferrerType,
+    schedule: &FeeSchedule,
+) -> Result<(u128, u128, u128)> {
+    let (referral_bps, project_bps, community_bps) = match referrer_type {
+        ReferrerType::User => (
+            schedule.referral_bps,
+            schedule.project_bps,
+            schedule.community_bps,
+        ),
+        ReferrerType::Project => (
+            0,
+            schedule.project_bps + schedule.referral_bps,
+            schedule.community_bps,
+        ),
+        ReferrerType::None => (
+            0,
+            schedule.project_bps + schedule.unreferred_project_bps,
+            schedule.community_bps + schedule.referral_bps - schedule.unreferred_project_bps,
+        ),
+    };
 
-    // Apply 6% fee (user gets 94%)
-    let net_return = gross_return
-        .checked_mul(9400) // 94%
-        .ok_or(error!(CurveError::ArithmeticOverflow))?
-        .checked_div(10000)
+    Ok((
+        apply_bps(amount, referral_bps)?,
+        apply_bps(amount, project_bps)?,
+        apply_bps(amount, community_bps)?,
+    ))
+}
+
+/// Split a routed project bucket into (creator, treasury)
+/// - Project: the folded-in referral share goes to the creator, who is the referrer
+/// - Otherwise the whole bucket goes to platform_treasury
+pub fn split_project_bucket(
+    amount: u128,
+    project: u128,
+    referrer_type: ReferrerType,
+    schedule: &FeeSchedule,
+) -> Result<(u128, u128)> {
+    let creator = match referrer_type {
+        ReferrerType::Project => apply_bps(amount, schedule.referral_bps)?,
+        ReferrerType::User | ReferrerType::None => 0,
+    };
+    let treasury = project
+        .checked_sub(creator)
         .ok_or(error!(CurveError::ArithmeticOverflow))?;
 
-    Ok(net_return)
+    Ok((creator, treasury))
 }
 
 /// V6 Fee distribution for buy transactions
-#[derive(Debug, Clone, Copy)]
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy)]
 pub struct V6BuyFeeDistribution {
     pub reserve: u128,           // 94%
     pub referral: u128,          // 3% (flexible routing)
@@ -231,76 +261,21 @@ pub struct V6BuyFeeDistribution {
 
 /// V6 Calculate fee distribution for a buy transaction
 /// Implements flexible routing logic based on referrer type
+/// Percentages come from the curve's fee schedule (defaults shown)
 pub fn calculate_v6_buy_fees(
     total_cost: u128,
     referrer_type: ReferrerType,
+    schedule: &FeeSchedule,
 ) -> Result<V6BuyFeeDistribution> {
     // 94% to reserve
-    let reserve = total_cost
-        .checked_mul(9400)
-        .ok_or(error!(CurveError::ArithmeticOverflow))?
-        .checked_div(10000)
-        .ok_or(error!(CurveError::ArithmeticOverflow))?;
+    let reserve = apply_bps(total_cost, schedule.reserve_bps)?;
 
     // 1% buyback/burn (always)
-    let buyback_burn = total_cost
-        .checked_mul(100)
-        .ok_or(error!(CurveError::ArithmeticOverflow))?
-        .checked_div(10000)
-        .ok_or(error!(CurveError::ArithmeticOverflow))?;
-
-    // 1% community rewards (base)
-    let base_community = total_cost
-        .checked_mul(100)
-        .ok_or(error!(CurveError::ArithmeticOverflow))?
-        .checked_div(10000)
-        .ok_or(error!(CurveError::ArithmeticOverflow))?;
+    let buyback_burn = apply_bps(total_cost, schedule.buyback_bps)?;
 
     // Flexible routing based on referrer type
-    let (referral, project, community_rewards) = match referrer_type {
-        ReferrerType::User => {
-            // User referral: 3% to user, 1% to project, 1% to community
-            let referral = total_cost
-                .checked_mul(300) // 3%
-                .ok_or(error!(CurveError::ArithmeticOverflow))?
-                .checked_div(10000)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?;
-
-            let project = total_cost
-                .checked_mul(100) // 1%
-                .ok_or(error!(CurveError::ArithmeticOverflow))?
-                .checked_div(10000)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?;
-
-            (referral, project, base_community)
-        },
-        ReferrerType::Project => {
-            // Project self-referral: 0% to referrer, 4% to project, 1% to community
-            let project = total_cost
-                .checked_mul(400) // 4% (3% + 1%)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?
-                .checked_div(10000)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?;
-
-            (0, project, base_community)
-        },
-        ReferrerType::None => {
-            // No referral: 0% to referrer, 2% to project, 3% to community
-            let project = total_cost
-                .checked_mul(200) // 2%
-                .ok_or(error!(CurveError::ArithmeticOverflow))?
-                .checked_div(10000)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?;
-
-            let community = total_cost
-                .checked_mul(300) // 3% (2% + 1%)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?
-                .checked_div(10000)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?;
-
-            (0, project, community)
-        }
-    };
+    let (referral, project, community_rewards) =
+        route_fee_buckets(total_cost, referrer_type, schedule)?;
 
     Ok(V6BuyFeeDistribution {
         reserve,
@@ -312,7 +287,7 @@ pub fn calculate_v6_buy_fees(
 }
 
 /// V6 Fee distribution for sell transactions
-#[derive(Debug, Clone, Copy)]
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy)]
 pub struct V6SellFeeDistribution {
     pub to_seller: u128,         // 94% (after 6% fee)
     pub referral: u128,          // Variable based on referrer type
@@ -325,78 +300,17 @@ pub struct V6SellFeeDistribution {
 pub fn calculate_v6_sell_fees(
     gross_return: u128,
     referrer_type: ReferrerType,
+    schedule: &FeeSchedule,
 ) -> Result<V6SellFeeDistribution> {
-    // Seller always gets 94%
-    let to_seller = gross_return
-        .checked_mul(9400)
-        .ok_or(error!(CurveError::ArithmeticOverflow))?
-        .checked_div(10000)
-        .ok_or(error!(CurveError::ArithmeticOverflow))?;
+    // Seller gets the reserve share (94%)
+    let to_seller = apply_bps(gross_return, schedule.reserve_bps)?;
 
     // 1% buyback/burn (always)
-    let buyback_burn = gross_return
-        .checked_mul(100)
-        .ok_or(error!(CurveError::ArithmeticOverflow))?
-        .checked_div(10000)
-        .ok_or(error!(CurveError::ArithmeticOverflow))?;
+    let buyback_burn = apply_bps(gross_return, schedule.buyback_bps)?;
 
     // Flexible routing for remaining 5%
-    let (referral, project, community_rewards) = match referrer_type {
-        ReferrerType::User => {
-            // User gets 3%, project gets 1%, community gets 1%
-            let referral = gross_return
-                .checked_mul(300)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?
-                .checked_div(10000)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?;
-
-            let project = gross_return
-                .checked_mul(100)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?
-                .checked_div(10000)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?;
-
-            let community = gross_return
-                .checked_mul(100)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?
-                .checked_div(10000)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?;
-
-            (referral, project, community)
-        },
-        ReferrerType::Project => {
-            // Project gets 4%, community gets 1%
-            let project = gross_return
-                .checked_mul(400)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?
-                .checked_div(10000)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?;
-
-            let community = gross_return
-                .checked_mul(100)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?
-                .checked_div(10000)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?;
-
-            (0, project, community)
-        },
-        ReferrerType::None => {
-            // Project gets 2%, community gets 3%
-            let project = gross_return
-                .checked_mul(200)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?
-                .checked_div(10000)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?;
-
-            let community = gross_return
-                .checked_mul(300)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?
-                .checked_div(10000)
-                .ok_or(error!(CurveError::ArithmeticOverflow))?;
-
-            (0, project, community)
-        }
-    };
+    let (referral, project, community_rewards) =
+        route_fee_buckets(gross_return, referrer_type, schedule)?;
 
     Ok(V6SellFeeDistribution {
         to_seller,
@@ -408,76 +322,23 @@ pub fn calculate_v6_sell_fees(
 }
 
 /// Referrer type for V6 fee routing
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq)]
 pub enum ReferrerType {
     User,     // Regular user referral
     Project,  // Project self-referral
     None,     // No referral
 }
 
-// ============================================================================
-// BACKWARD COMPATIBILITY WRAPPERS
-// ============================================================================
-
-/// Simplified fee structure for backward compatibility
-/// Maps V6's flexible routing to match existing transfer logic
-#[derive(Debug, Clone, Copy)]
-pub struct BuyFeeDistribution {
-    pub reserve: u128,           // 94%
-    pub instant_fee: u128,       // 3% referral (to referrer or creator)
-    pub buyback_burn: u128,      // 1%
-    pub community_rewards: u128, // 1%
-    pub platform: u128,          // 1% project fee (sent to platform_treasury)
-}
-
-/// Simplified sell fee structure
-#[derive(Debug, Clone, Copy)]
-pub struct SellFeeDistribution {
-    pub to_seller: u128,         // 94%
-    pub instant_fee: u128,       // 3% referral (to referrer or creator)
-    pub buyback_burn: u128,      // 1%
-    pub community_rewards: u128, // 1%
-    pub platform: u128,          // 1% project fee (sent to platform_treasury)
-}
-
-/// Wrapper for buy fees - assumes User referral by default
-/// For no referrer, use calculate_buy_fees_no_referrer()
-pub fn calculate_buy_fees(total_cost: u128) -> Result<BuyFeeDistribution> {
-    let v6_fees = calculate_v6_buy_fees(total_cost, ReferrerType::User)?;
-
-    Ok(BuyFeeDistribution {
-        reserve: v6_fees.reserve,
-        instant_fee: v6_fees.referral,    // 3% goes to referrer
-        buyback_burn: v6_fees.buyback_burn,
-        community_rewards: v6_fees.community_rewards,
-        platform: v6_fees.project,        // 1% project fee → platform_treasury
-    })
-}
-
-/// Buy fees when there's no referrer (routes to community)
-pub fn calculate_buy_fees_no_referrer(total_cost: u128) -> Result<BuyFeeDistribution> {
-    let v6_fees = calculate_v6_buy_fees(total_cost, ReferrerType::None)?;
-
-    Ok(BuyFeeDistribution {
-        reserve: v6_fees.reserve,
-        instant_fee: 0,                    // No referrer = no instant fee
-        buyback_burn: v6_fees.buyback_burn,
-        community_rewards: v6_fees.community_rewards, // 3% to community
-        platform: v6_fees.project,         // 2% project → platform_treasury
-    })
-}
-
-/// Wrapper for sell fees - assumes User referral by default
-pub fn calculate_sell_fees(gross_return: u128) -> Result<SellFeeDistribution> {
-    let v6_fees = calculate_v6_sell_fees(gross_return, ReferrerType::User)?;
-
-    Ok(SellFeeDistribution {
-        to_seller: v6_fees.to_seller,
-        instant_fee: v6_fees.referral,     // 3% to referrer
-        buyback_burn: v6_fees.buyback_burn,
-        community_rewards: v6_fees.community_rewards,
-        platform: v6_fees.project,         // 1% project → platform_treasury
-    })
+impl ReferrerType {
+    /// Derive routing from the referrer argument
+    /// Referrer == creator is a project self-referral
+    pub fn resolve(referrer: Option<Pubkey>, creator: &Pubkey) -> Self {
+        match referrer {
+            Some(referrer) if referrer == *creator => ReferrerType::Project,
+            Some(_) => ReferrerType::User,
+            None => ReferrerType::None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -487,7 +348,7 @@ mod tests {
     #[test]
     fn test_v6_buy_fees_user_referral() {
         let total_cost = 1_000_000_000u128; // 1 SOL
-        let fees = calculate_v6_buy_fees(total_cost, ReferrerType::User).unwrap();
+        let fees = calculate_v6_buy_fees(total_cost, ReferrerType::User, &FeeSchedule::default()).unwrap();
 
         // Check percentages
         assert_eq!(fees.reserve, 940_000_000); // 94%
@@ -505,7 +366,7 @@ mod tests {
     #[test]
     fn test_v6_buy_fees_project_referral() {
         let total_cost = 1_000_000_000u128; // 1 SOL
-        let fees = calculate_v6_buy_fees(total_cost, ReferrerType::Project).unwrap();
+        let fees = calculate_v6_buy_fees(total_cost, ReferrerType::Project, &FeeSchedule::default()).unwrap();
 
         // Check percentages
         assert_eq!(fees.reserve, 940_000_000); // 94%
@@ -523,7 +384,7 @@ mod tests {
     #[test]
     fn test_v6_buy_fees_no_referral() {
         let total_cost = 1_000_000_000u128; // 1 SOL
-        let fees = calculate_v6_buy_fees(total_cost, ReferrerType::None).unwrap();
+        let fees = calculate_v6_buy_fees(total_cost, ReferrerType::None, &FeeSchedule::default()).unwrap();
 
         // Check percentages
         assert_eq!(fees.reserve, 940_000_000); // 94%
@@ -538,10 +399,81 @@ mod tests {
         assert_eq!(sum, total_cost);
     }
 
+    #[test]
+    fn test_v6_buy_fees_custom_schedule() {
+        // 95% reserve, 2% referral, 1% project, 1% buyback, 1% community,
+        // no-referral split 0.5% project / 1.5% community
+        let schedule = FeeSchedule {
+            reserve_bps: 9500,
+            referral_bps: 200,
+            project_bps: 100,
+            buyback_bps: 100,
+            community_bps: 100,
+            unreferred_project_bps: 50,
+        };
+        assert!(schedule.validate().is_ok());
+
+        let total_cost = 1_000_000_000u128;
+        let fees = calculate_v6_buy_fees(total_cost, ReferrerType::None, &schedule).unwrap();
+
+        assert_eq!(fees.reserve, 950_000_000);
+        assert_eq!(fees.referral, 0);
+        assert_eq!(fees.project, 15_000_000);
+        assert_eq!(fees.buyback_burn, 10_000_000);
+        assert_eq!(fees.community_rewards, 25_000_000);
+    }
+
+    #[test]
+    fn test_fee_schedule_validation() {
+        assert!(FeeSchedule::default().validate().is_ok());
+
+        let schedule = FeeSchedule {
+            reserve_bps: 9500, // sums to 10100
+            ..FeeSchedule::default()
+        };
+        assert!(schedule.validate().is_err());
+
+        let schedule = FeeSchedule {
+            unreferred_project_bps: crate::REFERRAL_BPS as u16 + 1,
+            ..FeeSchedule::default()
+        };
+        assert!(schedule.validate().is_err());
+    }
+
+    #[test]
+    fn test_referrer_type_resolve() {
+        let creator = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+
+        assert_eq!(ReferrerType::resolve(Some(user), &creator), ReferrerType::User);
+        assert_eq!(ReferrerType::resolve(Some(creator), &creator), ReferrerType::Project);
+        assert_eq!(ReferrerType::resolve(None, &creator), ReferrerType::None);
+    }
+
+    #[test]
+    fn test_split_project_bucket() {
+        let total_cost = 1_000_000_000u128; // 1 SOL
+        let schedule = FeeSchedule::default();
+
+        // Self-referral: creator gets the 3% referral share, treasury keeps 1%
+        let fees = calculate_v6_buy_fees(total_cost, ReferrerType::Project, &schedule).unwrap();
+        let (creator, treasury) =
+            split_project_bucket(total_cost, fees.project, ReferrerType::Project, &schedule).unwrap();
+        assert_eq!(creator, 30_000_000);
+        assert_eq!(treasury, 10_000_000);
+
+        // No referral: the whole 2% project bucket goes to treasury
+        let fees = calculate_v6_buy_fees(total_cost, ReferrerType::None, &schedule).unwrap();
+        let (creator, treasury) =
+            split_project_bucket(total_cost, fees.project, ReferrerType::None, &schedule).unwrap();
+        assert_eq!(creator, 0);
+        assert_eq!(treasury, 20_000_000);
+    }
+
     #[test]
     fn test_v6_sell_fees_user_referral() {
         let gross = 1_000_000_000u128; // 1 SOL gross
-        let fees = calculate_v6_sell_fees(gross, ReferrerType::User).unwrap();
+        let fees = calculate_v6_sell_fees(gross, ReferrerType::User, &FeeSchedule::default()).unwrap();
 
         // Seller should get 94%
         assert_eq!(fees.to_seller, 940_000_000);
@@ -555,4 +487,149 @@ mod tests {
                     fees.buyback_burn + fees.community_rewards;
         assert_eq!(total, gross);
     }
+
+    // ========================================================================
+    // Reference implementation: per-key summation (the original V6 loop)
+    // ========================================================================
+
+    fn reference_buy_cost(current_supply: u128, amount: u64) -> Result<u128> {
+        let mut total_cost = 0u128;
+
+        // Sum prices for each key from current_supply to (current_supply + amount)
+        for i in 0..amount {
+            let supply_at_i = current_supply
+                .checked_add(i as u128)
+                .ok_or(error!(CurveError::ArithmeticOverflow))?;
+
+            let price = calculate_price_at_supply(supply_at_i)?;
+
+            total_cost = total_cost
+                .checked_add(price)
+                .ok_or(error!(CurveError::ArithmeticOverflow))?;
+        }
+
+        Ok(total_cost)
+    }
+
+    fn reference_sell_return(current_supply: u128, amount: u64) -> Result<u128> {
+        let mut gross_return = 0u128;
+
+        // Sum prices for each key being sold (in reverse order)
+        for i in 0..amount {
+            let supply_at_i = current_supply
+                .checked_sub((i + 1) as u128)
+                .ok_or(error!(CurveError::ArithmeticOverflow))?;
+
+            let price = calculate_price_at_supply(supply_at_i)?;
+
+            gross_return = gross_return
+                .checked_add(price)
+                .ok_or(error!(CurveError::ArithmeticOverflow))?;
+        }
+
+        Ok(gross_return * 9400 / 10000)
+    }
+
+    /// Documented closed-form tolerance: 1 lamport per key + 1 ppm of cost
+    fn assert_within_tolerance(closed: u128, reference: u128, amount: u64) {
+        let tolerance = amount as u128 + reference / 1_000_000;
+        let diff = closed.abs_diff(reference);
+        assert!(
+            diff <= tolerance,
+            "closed form {} vs reference {} (diff {} > tolerance {})",
+            closed, reference, diff, tolerance
+        );
+    }
+
+    #[test]
+    fn test_closed_form_buy_matches_reference() {
+        for &(supply, amount) in &[
+            (0u128, 1u64),
+            (0, 10),
+            (0, 100),
+            (1, 1),
+            (5, 3),
+            (10, 50),
+            (100, 50),
+            (999, 2),
+            (1_001, 20),
+            (1_500, 100),
+            (5_000, 37),
+            (25_000, 100),
+            (100_000, 250),
+            (1_000_000, 100),
+        ] {
+            let closed = calculate_buy_cost(supply, amount).unwrap();
+            let reference = reference_buy_cost(supply, amount).unwrap();
+            assert_within_tolerance(closed, reference, amount);
+        }
+    }
+
+    #[test]
+    fn test_closed_form_sell_matches_reference() {
+        for &(supply, amount) in &[
+            (1u128, 1u64),
+            (20, 20),
+            (150, 100),
+            (1_101, 100),
+            (2_000, 1),
+            (10_000, 50),
+            (100_000, 250),
+        ] {
+            let closed = calculate_sell_return(supply, amount, &FeeSchedule::default()).unwrap();
+            let reference = reference_sell_return(supply, amount).unwrap();
+            assert_within_tolerance(closed, reference, amount);
+        }
+    }
+
+    #[test]
+    fn test_price_at_supply_known_values() {
+        // P(0) = 0.05 SOL
+        assert_eq!(calculate_price_at_supply(0).unwrap(), 50_000_000);
+        // P(32) = 0.05 + 0.0096 + 0.0000012 * 256 = 0.0599072 SOL
+        assert_eq!(calculate_price_at_supply(32).unwrap(), 59_907_200);
+        // P(100000) = 0.05 + 30 + 0.0000012 * 10^8 = 150.05 SOL
+        assert_eq!(calculate_price_at_supply(100_000).unwrap(), 150_050_000_000);
+    }
+
+    #[test]
+    fn test_closed_form_buy_then_sell_round_trip() {
+        let supply = 2_000u128;
+        let cost = calculate_buy_cost(supply, 40).unwrap();
+        let gross = calculate_sell_return(supply + 40, 40, &FeeSchedule::default()).unwrap() * 10000 / 9400;
+
+        // Gross sell return walks back over the same keys
+        assert!(cost.abs_diff(gross) <= 1);
+    }
+
+    #[test]
+    fn test_keys_for_budget_inverts_cost() {
+        for &(supply, budget) in &[
+            (0u128, 1_000_000_000u128),
+            (50, 2_000_000_000),
+            (1_000, 10_000_000_000),
+            (20_000, 500_000_000_000),
+        ] {
+            let keys = calculate_keys_for_budget(supply, budget, 1_000).unwrap();
+            assert!(calculate_buy_cost(supply, keys).unwrap() <= budget);
+            assert!(calculate_buy_cost(supply, keys + 1).unwrap() > budget);
+        }
+    }
+
+    #[test]
+    fn test_keys_for_budget_respects_cap() {
+        // Budget is plenty for 100 keys, cap limits to 20
+        assert_eq!(calculate_keys_for_budget(0, 1_000_000_000_000, 20).unwrap(), 20);
+        // Budget just below / exactly one key's price
+        let one_key = calculate_buy_cost(0, 1).unwrap();
+        assert_eq!(calculate_keys_for_budget(0, one_key - 1, 100).unwrap(), 0);
+        assert_eq!(calculate_keys_for_budget(0, one_key, 100).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_closed_form_zero_amount() {
+        assert_eq!(calculate_buy_cost(1_234, 0).unwrap(), 0);
+        assert_eq!(calculate_sell_return(1_234, 0, &FeeSchedule::default()).unwrap(), 0);
+        assert!(calculate_sell_return(10, 11, &FeeSchedule::default()).is_err());
+    }
 }
\ No newline at end of file
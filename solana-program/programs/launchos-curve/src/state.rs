@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::math_v6::{V6BuyFeeDistribution, V6SellFeeDistribution};
 
 /// Status of the bonding curve
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
@@ -11,6 +12,8 @@ pub enum CurveStatus {
     Frozen,
     /// Token minted and launched on Pump.fun
     Launched,
+    /// Frozen but never launched: holders redeem keys pro-rata with claim_refund
+    Refunding,
 }
 
 /// Type of curve (Profile vs Project)
@@ -22,6 +25,121 @@ pub enum CurveType {
     Project,
 }
 
+/// What caused a curve to freeze
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FreezeTrigger {
+    /// Creator called freeze_curve
+    Manual,
+    /// Reserve reached target_reserve (buy or crank_freeze)
+    ReserveThreshold,
+    /// launch_ts passed (crank_freeze)
+    TimeBased,
+    /// Admin emergency_freeze
+    AdminEmergency,
+}
+
+impl FreezeTrigger {
+    /// Map a pre-enum `Option<String>` trigger to its variant
+    pub fn from_legacy(trigger: &str) -> Option<Self> {
+        match trigger {
+            "manual" => Some(Self::Manual),
+            "reserve_threshold" => Some(Self::ReserveThreshold),
+            "time_based" => Some(Self::TimeBased),
+            "admin_emergency" => Some(Self::AdminEmergency),
+            _ => None,
+        }
+    }
+}
+
+/// Fee schedule in basis points (V6 buckets, must sum to 10000)
+/// Stored in CurveConfig and copied onto each curve at creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FeeSchedule {
+    /// Reserve share of buys / seller share of sells (94%)
+    pub reserve_bps: u16,
+
+    /// Referral bucket, routed by referrer type (3%)
+    pub referral_bps: u16,
+
+    /// Project minimum (1%)
+    pub project_bps: u16,
+
+    /// Buyback/burn (1%)
+    pub buyback_bps: u16,
+
+    /// Community rewards (1%)
+    pub community_bps: u16,
+
+    /// Part of the referral bucket sent to the project when there is no referrer (1%)
+    /// The rest of the bucket goes to community
+    pub unreferred_project_bps: u16,
+}
+
+impl FeeSchedule {
+    pub const LEN: usize = 2 * 6;
+
+    /// Buckets must sum to 10000 and the no-referral split must fit the referral bucket
+    pub fn validate(&self) -> Result<()> {
+        let total = self.reserve_bps as u128
+            + self.referral_bps as u128
+            + self.project_bps as u128
+            + self.buyback_bps as u128
+            + self.community_bps as u128;
+
+        require!(
+            total == crate::BPS_DENOMINATOR,
+            crate::errors::CurveError::InvalidFeeSchedule
+        );
+        require!(
+            self.unreferred_project_bps <= self.referral_bps,
+            crate::errors::CurveError::InvalidFeeSchedule
+        );
+        Ok(())
+    }
+}
+
+impl Default for FeeSchedule {
+    /// V6 defaults: 94 / 3 / 1 / 1 / 1
+    fn default() -> Self {
+        Self {
+            reserve_bps: crate::RESERVE_BPS as u16,
+            referral_bps: crate::REFERRAL_BPS as u16,
+            project_bps: crate::PROJECT_BPS as u16,
+            buyback_bps: crate::BUYBACK_BPS as u16,
+            community_bps: crate::COMMUNITY_BPS as u16,
+            unreferred_project_bps: crate::UNREFERRED_PROJECT_BPS as u16,
+        }
+    }
+}
+
+/// Granular pause switches, set by the admin on CurveConfig
+/// The global `paused` switch blocks every path regardless of these flags
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct PauseFlags {
+    /// buy_keys, buy_keys_exact_in, sell_keys
+    pub trading: bool,
+
+    /// create_curve, creator_initial_buy, activate_curve
+    pub creation: bool,
+
+    /// launch
+    pub launches: bool,
+
+    /// claim_tokens, claim_fees, claim_referral_fees
+    pub claims: bool,
+}
+
+impl PauseFlags {
+    pub const LEN: usize = 4;
+}
+
+/// Side of a trade (used by the read-only quote instruction)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
 /// Main bonding curve account
 #[account]
 pub struct BondingCurve {
@@ -45,7 +163,7 @@ pub struct BondingCurve {
     /// Total SOL in reserve vault
     pub reserve_balance: u128,
 
-    /// Number of unique key holders
+    /// Number of key holders with a non-zero balance
     pub unique_holders: u32,
 
     // ===== Timestamps =====
@@ -68,8 +186,8 @@ pub struct BondingCurve {
     /// Timestamp when curve was frozen
     pub freeze_ts: Option<i64>,
 
-    /// Trigger that caused freeze: "manual", "reserve_threshold", "time_based"
-    pub freeze_trigger: Option<String>,
+    /// Trigger that caused freeze
+    pub freeze_trigger: Option<FreezeTrigger>,
 
     /// Supply at time of freeze (locked for snapshot)
     pub supply_at_freeze: u128,
@@ -137,6 +255,106 @@ pub struct BondingCurve {
 
     /// Curve account bump
     pub bump: u8,
+
+    // ===== Fees =====
+    /// Fee schedule in force when the curve was created (never changed retroactively)
+    pub fee_schedule: FeeSchedule,
+
+    // ===== Admin =====
+    /// Per-curve pause set by the admin (blocks trading, activation and launch)
+    pub admin_paused: bool,
+}
+
+/// Full pre-upgrade BondingCurve layout (string trigger, no fee schedule or admin pause)
+/// Used by migrate_curve to rewrite pre-upgrade curves
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyBondingCurve {
+    pub creator: Pubkey,
+    pub twitter_handle: String,
+    pub curve_type: CurveType,
+    pub status: CurveStatus,
+    pub supply: u128,
+    pub reserve_balance: u128,
+    pub unique_holders: u32,
+    pub created_at: i64,
+    pub activated_at: i64,
+    pub creator_unlock_time: i64,
+    pub launch_ts: Option<i64>,
+    pub target_reserve: u64,
+    pub freeze_ts: Option<i64>,
+    pub freeze_trigger: Option<String>,
+    pub supply_at_freeze: u128,
+    pub reserve_at_freeze: u128,
+    pub snapshot_root: Option<[u8; 32]>,
+    pub token_mint: Option<Pubkey>,
+    pub lp_vault: Option<Pubkey>,
+    pub marketing_wallet: Option<Pubkey>,
+    pub utility_wallet: Option<Pubkey>,
+    pub launched_at: Option<i64>,
+    pub initial_buy_amount: u128,
+    pub is_launching: bool,
+    pub creator_fees_collected: u128,
+    pub rewards_fees_collected: u128,
+    pub total_buys: u64,
+    pub total_sells: u64,
+    pub platform_treasury: Pubkey,
+    pub buyback_wallet: Pubkey,
+    pub community_wallet: Pubkey,
+    pub reentrancy_guard: bool,
+    pub reserve_bump: u8,
+    pub bump: u8,
+}
+
+impl LegacyBondingCurve {
+    /// Current layout: enum trigger, default fee schedule, not admin-paused
+    pub fn migrate(self) -> Result<BondingCurve> {
+        let freeze_trigger = self
+            .freeze_trigger
+            .map(|legacy| {
+                FreezeTrigger::from_legacy(&legacy)
+                    .ok_or(crate::errors::CurveError::InvalidFreezeTrigger)
+            })
+            .transpose()?;
+
+        Ok(BondingCurve {
+            creator: self.creator,
+            twitter_handle: self.twitter_handle,
+            curve_type: self.curve_type,
+            status: self.status,
+            supply: self.supply,
+            reserve_balance: self.reserve_balance,
+            unique_holders: self.unique_holders,
+            created_at: self.created_at,
+            activated_at: self.activated_at,
+            creator_unlock_time: self.creator_unlock_time,
+            launch_ts: self.launch_ts,
+            target_reserve: self.target_reserve,
+            freeze_ts: self.freeze_ts,
+            freeze_trigger,
+            supply_at_freeze: self.supply_at_freeze,
+            reserve_at_freeze: self.reserve_at_freeze,
+            snapshot_root: self.snapshot_root,
+            token_mint: self.token_mint,
+            lp_vault: self.lp_vault,
+            marketing_wallet: self.marketing_wallet,
+            utility_wallet: self.utility_wallet,
+            launched_at: self.launched_at,
+            initial_buy_amount: self.initial_buy_amount,
+            is_launching: self.is_launching,
+            creator_fees_collected: self.creator_fees_collected,
+            rewards_fees_collected: self.rewards_fees_collected,
+            total_buys: self.total_buys,
+            total_sells: self.total_sells,
+            platform_treasury: self.platform_treasury,
+            buyback_wallet: self.buyback_wallet,
+            community_wallet: self.community_wallet,
+            reentrancy_guard: self.reentrancy_guard,
+            reserve_bump: self.reserve_bump,
+            bump: self.bump,
+            fee_schedule: FeeSchedule::default(),
+            admin_paused: false,
+        })
+    }
 }
 
 impl BondingCurve {
@@ -159,7 +377,7 @@ impl BondingCurve {
         (1 + 8) + // launch_ts (Option<i64>)
         8 + // target_reserve (u64)
         (1 + 8) + // freeze_ts (Option<i64>)
-        (1 + 4 + 20) + // freeze_trigger (Option<String> max 20 chars)
+        (1 + 1) + // freeze_trigger (Option<FreezeTrigger>)
         16 + // supply_at_freeze (u128)
         16 + // reserve_at_freeze (u128)
         // Snapshot
@@ -186,7 +404,11 @@ impl BondingCurve {
         1 + // reentrancy_guard (bool)
         // PDA Bumps
         1 + // reserve_bump (u8)
-        1; // bump (u8)
+        1 + // bump (u8)
+        // Fees
+        FeeSchedule::LEN + // fee_schedule
+        // Admin
+        1; // admin_paused
     // TOTAL: ~550 bytes (rounded up to 600 for safety)
 
     /// SECURITY: Check if reentrancy guard is active
@@ -206,10 +428,10 @@ impl BondingCurve {
         crate::math::calculate_buy_cost(self.supply, amount)
     }
 
-    /// Calculate sell return with unified 6% fee
+    /// Calculate sell return with the curve's unified fee (6% by default)
     /// SECURITY: Uses checked arithmetic via math module
     pub fn calculate_sell_price(&self, amount: u64) -> Result<u128> {
-        crate::math::calculate_sell_return(self.supply, amount)
+        crate::math::calculate_sell_return(self.supply, amount, &self.fee_schedule)
     }
 
     /// SECURITY: Validate amount is within acceptable range
@@ -248,7 +470,13 @@ impl BondingCurve {
 
         let one_percent = (self.supply / 100) as u64;
 
-        one_percent.max(MIN_KEYS).min(MAX_KEYS)
+        one_percent.clamp(MIN_KEYS, MAX_KEYS)
+    }
+
+    /// SECURITY: Reject while the admin has paused this curve
+    pub fn require_not_paused(&self) -> Result<()> {
+        require!(!self.admin_paused, crate::errors::CurveError::CurvePaused);
+        Ok(())
     }
 
     /// Check if keys are locked (for creator)
@@ -276,16 +504,16 @@ impl BondingCurve {
     }
 
     /// Execute freeze (shared logic for all freeze triggers)
-    pub fn execute_freeze(&mut self, trigger: &str, current_time: i64) -> Result<()> {
+    pub fn execute_freeze(&mut self, trigger: FreezeTrigger, current_time: i64) -> Result<()> {
         require!(self.can_freeze(), crate::errors::CurveError::AlreadyFrozen);
 
         self.status = CurveStatus::Frozen;
         self.freeze_ts = Some(current_time);
-        self.freeze_trigger = Some(trigger.to_string());
+        self.freeze_trigger = Some(trigger);
         self.supply_at_freeze = self.supply;
         self.reserve_at_freeze = self.reserve_balance;
 
-        msg!("🧊 Curve FROZEN | Trigger: {} | Supply: {} | Reserve: {} lamports",
+        msg!("🧊 Curve FROZEN | Trigger: {:?} | Supply: {} | Reserve: {} lamports",
             trigger,
             self.supply,
             self.reserve_balance
@@ -294,6 +522,24 @@ impl BondingCurve {
         Ok(())
     }
 
+    /// Check if an unlaunched frozen curve is past its refund deadline
+    pub fn is_refund_deadline_passed(&self, refund_delay: i64, current_time: i64) -> bool {
+        match self.freeze_ts {
+            Some(freeze_ts) => current_time >= freeze_ts.saturating_add(refund_delay),
+            None => false,
+        }
+    }
+
+    /// Lamports refunded for `amount` keys (pro-rata against the reserve at freeze, rounded down)
+    pub fn calculate_refund(&self, amount: u64) -> Result<u128> {
+        require!(self.supply_at_freeze > 0, crate::errors::CurveError::InvalidAmount);
+
+        self.reserve_at_freeze
+            .checked_mul(amount as u128)
+            .map(|product| product / self.supply_at_freeze)
+            .ok_or(error!(crate::errors::CurveError::ArithmeticOverflow))
+    }
+
     /// Check if curve can be launched
     pub fn can_launch(&self) -> bool {
         self.status == CurveStatus::Frozen
@@ -323,6 +569,9 @@ pub struct KeyHolder {
 
     /// Bump seed for PDA
     pub bump: u8,
+
+    /// Referral code account this holder is bound to (sticky, set at first buy)
+    pub referrer: Option<Pubkey>,
 }
 
 impl KeyHolder {
@@ -332,6 +581,194 @@ impl KeyHolder {
         8 + // amount
         8 + // acquired_at
         1 + // is_creator
+        1 + // bump
+        (1 + 32); // referrer (Option<Pubkey>)
+}
+
+/// KeyHolder layout before referral binding (no `referrer`)
+/// Used by migrate_key_holder to grow pre-upgrade holders
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyKeyHolder {
+    pub owner: Pubkey,
+    pub curve: Pubkey,
+    pub amount: u64,
+    pub acquired_at: i64,
+    pub is_creator: bool,
+    pub bump: u8,
+}
+
+impl LegacyKeyHolder {
+    /// Current layout, unbound (`referrer = None`)
+    pub fn migrate(self) -> KeyHolder {
+        KeyHolder {
+            owner: self.owner,
+            curve: self.curve,
+            amount: self.amount,
+            acquired_at: self.acquired_at,
+            is_creator: self.is_creator,
+            bump: self.bump,
+            referrer: None,
+        }
+    }
+}
+
+/// Referral code registered on a curve
+/// PDA: ["referrer", curve, code]
+#[account]
+pub struct Referrer {
+    /// Curve this code refers buyers to
+    pub curve: Pubkey,
+
+    /// Wallet that registered the code and receives referral fees
+    pub owner: Pubkey,
+
+    /// Short referral code (unique per curve)
+    pub code: String,
+
+    /// Number of holders bound to this code
+    pub referred_holders: u32,
+
+    /// Lifetime referral fees earned on this curve (lamports)
+    pub total_earned: u128,
+
+    /// Referral fees accrued in the curve's FeeVault, not yet claimed (lamports)
+    pub unclaimed_fees: u128,
+
+    /// Timestamp when code was registered
+    pub created_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Referrer {
+    pub const MIN_CODE_LEN: usize = 3;
+    pub const MAX_CODE_LEN: usize = 16;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // curve
+        32 + // owner
+        (4 + Self::MAX_CODE_LEN) + // code
+        4 + // referred_holders
+        16 + // total_earned
+        16 + // unclaimed_fees
+        8 + // created_at
+        1; // bump
+
+    /// Codes are 3-16 ASCII letters, digits, '_' or '-'
+    pub fn is_valid_code(code: &str) -> bool {
+        (Self::MIN_CODE_LEN..=Self::MAX_CODE_LEN).contains(&code.len())
+            && code
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+    }
+}
+
+/// Per-curve fee accrual vault (pull-based fee distribution)
+/// Trades credit the ledgers here instead of paying every recipient;
+/// each recipient withdraws its balance with claim_fees / claim_referral_fees
+/// PDA: ["fee_vault", curve]
+#[account]
+pub struct FeeVault {
+    /// Curve whose trades accrue here
+    pub curve: Pubkey,
+
+    /// Self-referral share of the project bucket owed to the creator (lamports)
+    pub creator_owed: u128,
+
+    /// Project bucket owed to the platform treasury (lamports)
+    pub treasury_owed: u128,
+
+    /// Buyback/burn bucket owed to the buyback wallet (lamports)
+    pub buyback_owed: u128,
+
+    /// Community bucket owed to the community wallet (lamports)
+    pub community_owed: u128,
+
+    /// Sum of unclaimed referral balances across this curve's Referrer accounts (lamports)
+    pub referral_owed: u128,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl FeeVault {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // curve
+        16 + // creator_owed
+        16 + // treasury_owed
+        16 + // buyback_owed
+        16 + // community_owed
+        16 + // referral_owed
+        1; // bump
+
+    /// Credit one trade's fee buckets, returning the lamports to move into the vault
+    pub fn accrue(
+        &mut self,
+        creator: u128,
+        treasury: u128,
+        buyback: u128,
+        community: u128,
+        referral: u128,
+    ) -> Result<u128> {
+        let overflow = || error!(crate::errors::CurveError::ArithmeticOverflow);
+
+        self.creator_owed = self.creator_owed.checked_add(creator).ok_or_else(overflow)?;
+        self.treasury_owed = self.treasury_owed.checked_add(treasury).ok_or_else(overflow)?;
+        self.buyback_owed = self.buyback_owed.checked_add(buyback).ok_or_else(overflow)?;
+        self.community_owed = self.community_owed.checked_add(community).ok_or_else(overflow)?;
+        self.referral_owed = self.referral_owed.checked_add(referral).ok_or_else(overflow)?;
+
+        creator
+            .checked_add(treasury)
+            .and_then(|total| total.checked_add(buyback))
+            .and_then(|total| total.checked_add(community))
+            .and_then(|total| total.checked_add(referral))
+            .ok_or_else(overflow)
+    }
+
+    /// Take a recipient's whole balance, zeroing its ledger
+    pub fn take_owed(&mut self, recipient: FeeRecipient) -> u128 {
+        let owed = match recipient {
+            FeeRecipient::Creator => &mut self.creator_owed,
+            FeeRecipient::Treasury => &mut self.treasury_owed,
+            FeeRecipient::Buyback => &mut self.buyback_owed,
+            FeeRecipient::Community => &mut self.community_owed,
+            FeeRecipient::Referrer => &mut self.referral_owed,
+        };
+        std::mem::take(owed)
+    }
+}
+
+/// Fee ledger a claim withdraws from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FeeRecipient {
+    Creator,
+    Treasury,
+    Buyback,
+    Community,
+    Referrer,
+}
+
+/// Lamports set aside to reward crank_freeze keepers
+/// Funded explicitly via fund_keeper_pool (anyone can top it up); trade fees never flow here
+/// PDA: ["keeper_pool"]
+#[account]
+pub struct KeeperPool {
+    /// Lamports deposited through fund_keeper_pool
+    pub total_funded: u64,
+
+    /// Lamports paid out as keeper rewards
+    pub total_paid: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl KeeperPool {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // total_funded
+        8 + // total_paid
         1; // bump
 }
 
@@ -367,6 +804,24 @@ pub struct CurveConfig {
 
     /// Bump seed
     pub bump: u8,
+
+    /// Fee schedule applied to newly created curves
+    pub fee_schedule: FeeSchedule,
+
+    /// Granular pause switches (trading, creation, launches, claims)
+    pub pause_flags: PauseFlags,
+
+    /// Authority proposed by the current admin, pending acceptance
+    pub pending_authority: Option<Pubkey>,
+
+    /// Delay before a queued admin action can execute (seconds)
+    pub timelock_delay: i64,
+
+    /// Id of the next queued admin action (PendingAdminAction PDA seed)
+    pub admin_action_nonce: u64,
+
+    /// Seconds after freeze_ts before anyone can move an unlaunched curve to Refunding
+    pub refund_delay: i64,
 }
 
 impl CurveConfig {
@@ -380,17 +835,244 @@ impl CurveConfig {
         8 + // lock_period
         8 + // target_reserve_default
         1 + // paused
+        1 + // bump
+        FeeSchedule::LEN + // fee_schedule
+        PauseFlags::LEN + // pause_flags
+        (1 + 32) + // pending_authority (Option<Pubkey>)
+        8 + // timelock_delay
+        8 + // admin_action_nonce
+        8; // refund_delay
+    // TOTAL: 243 bytes
+
+    /// Current values of the admin-editable settings
+    pub fn params(&self) -> ConfigParams {
+        ConfigParams {
+            platform_treasury: self.platform_treasury,
+            buyback_wallet: self.buyback_wallet,
+            community_wallet: self.community_wallet,
+            max_purchase: self.max_purchase,
+            creator_min_buy: self.creator_min_buy,
+            lock_period: self.lock_period,
+            target_reserve_default: self.target_reserve_default,
+            refund_delay: self.refund_delay,
+        }
+    }
+
+    /// Apply a limits update, leaving unset fields unchanged
+    /// Only affects curves created afterwards (curves copy the target reserve at creation)
+    pub fn apply_update(&mut self, update: &ConfigUpdate) -> Result<()> {
+        if let Some(max_purchase) = update.max_purchase {
+            self.max_purchase = max_purchase;
+        }
+        if let Some(creator_min_buy) = update.creator_min_buy {
+            self.creator_min_buy = creator_min_buy;
+        }
+        if let Some(lock_period) = update.lock_period {
+            self.lock_period = lock_period;
+        }
+        if let Some(target_reserve_default) = update.target_reserve_default {
+            self.target_reserve_default = target_reserve_default;
+        }
+
+        // SECURITY: Creator must be able to meet the minimum within one purchase
+        require!(
+            self.max_purchase > 0
+                && self.creator_min_buy > 0
+                && self.creator_min_buy <= self.max_purchase
+                && self.lock_period >= 0
+                && self.target_reserve_default > 0,
+            crate::errors::CurveError::InvalidConfig
+        );
+        Ok(())
+    }
+
+    /// Apply a fee wallet change, leaving unset wallets unchanged (timelocked)
+    /// Only affects curves created afterwards (curves copy wallets at creation)
+    pub fn apply_wallet_update(&mut self, update: &WalletUpdate) {
+        if let Some(platform_treasury) = update.platform_treasury {
+            self.platform_treasury = platform_treasury;
+        }
+        if let Some(buyback_wallet) = update.buyback_wallet {
+            self.buyback_wallet = buyback_wallet;
+        }
+        if let Some(community_wallet) = update.community_wallet {
+            self.community_wallet = community_wallet;
+        }
+    }
+
+    /// SECURITY: Reject trades while paused globally or for trading
+    pub fn require_trading_enabled(&self) -> Result<()> {
+        require!(
+            !self.paused && !self.pause_flags.trading,
+            crate::errors::CurveError::TradingPaused
+        );
+        Ok(())
+    }
+
+    /// SECURITY: Reject curve creation while paused globally or for creation
+    pub fn require_creation_enabled(&self) -> Result<()> {
+        require!(
+            !self.paused && !self.pause_flags.creation,
+            crate::errors::CurveError::CreationPaused
+        );
+        Ok(())
+    }
+
+    /// SECURITY: Reject launches while paused globally or for launches
+    pub fn require_launches_enabled(&self) -> Result<()> {
+        require!(
+            !self.paused && !self.pause_flags.launches,
+            crate::errors::CurveError::LaunchesPaused
+        );
+        Ok(())
+    }
+
+    /// SECURITY: Reject claims while paused globally or for claims
+    pub fn require_claims_enabled(&self) -> Result<()> {
+        require!(
+            !self.paused && !self.pause_flags.claims,
+            crate::errors::CurveError::ClaimsPaused
+        );
+        Ok(())
+    }
+}
+
+/// Admin-editable CurveConfig settings (recorded in ConfigUpdatedEvent)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ConfigParams {
+    pub platform_treasury: Pubkey,
+    pub buyback_wallet: Pubkey,
+    pub community_wallet: Pubkey,
+    pub max_purchase: u64,
+    pub creator_min_buy: u64,
+    pub lock_period: i64,
+    pub target_reserve_default: u64,
+    pub refund_delay: i64,
+}
+
+/// update_config argument (curve limits, applied instantly): `None` leaves a setting unchanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct ConfigUpdate {
+    pub max_purchase: Option<u64>,
+    pub creator_min_buy: Option<u64>,
+    pub lock_period: Option<i64>,
+    pub target_reserve_default: Option<u64>,
+}
+
+/// Fee wallet change queued through the admin timelock: `None` leaves a wallet unchanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct WalletUpdate {
+    pub platform_treasury: Option<Pubkey>,
+    pub buyback_wallet: Option<Pubkey>,
+    pub community_wallet: Option<Pubkey>,
+}
+
+/// Sensitive config change, applied only through the admin timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub enum AdminActionKind {
+    /// Treasury/buyback/community wallets (limits go through update_config)
+    UpdateWallets(WalletUpdate),
+
+    /// Fee split for newly created curves
+    UpdateFeeSchedule(FeeSchedule),
+
+    /// Start an authority handover (None cancels a pending one)
+    ProposeAuthority(Option<Pubkey>),
+
+    /// Change the timelock delay itself (never below ADMIN_TIMELOCK_DEFAULT)
+    SetTimelockDelay(i64),
+
+    /// Change how long after freeze anyone may start a refund (must be positive)
+    SetRefundDelay(i64),
+}
+
+impl AdminActionKind {
+    /// Largest variant: UpdateWallets (3 Option<Pubkey>)
+    pub const LEN: usize = 1 + // variant
+        3 * (1 + 32); // wallets
+}
+
+/// Queued admin action, executable after the config's timelock delay
+/// PDA: ["admin_action", id]
+#[account]
+pub struct PendingAdminAction {
+    /// Sequential id (config.admin_action_nonce at queue time)
+    pub id: u64,
+
+    /// Change to apply
+    pub action: AdminActionKind,
+
+    /// Authority that queued the action
+    pub proposer: Pubkey,
+
+    /// Timestamp when queued
+    pub queued_at: i64,
+
+    /// Earliest execution timestamp
+    pub executable_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PendingAdminAction {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // id
+        AdminActionKind::LEN + // action
+        32 + // proposer
+        8 + // queued_at
+        8 + // executable_at
         1; // bump
-    // TOTAL: 170 bytes (pad to 200 for safety)
+}
+
+/// CurveConfig layout before fee schedules, pause flags, handover, timelock and refunds
+/// Used by migrate_config to grow the pre-upgrade config
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyCurveConfig {
+    pub authority: Pubkey,
+    pub platform_treasury: Pubkey,
+    pub buyback_wallet: Pubkey,
+    pub community_wallet: Pubkey,
+    pub max_purchase: u64,
+    pub creator_min_buy: u64,
+    pub lock_period: i64,
+    pub target_reserve_default: u64,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+impl LegacyCurveConfig {
+    /// Current layout with the same defaults as `initialize`
+    pub fn migrate(self) -> CurveConfig {
+        CurveConfig {
+            authority: self.authority,
+            platform_treasury: self.platform_treasury,
+            buyback_wallet: self.buyback_wallet,
+            community_wallet: self.community_wallet,
+            max_purchase: self.max_purchase,
+            creator_min_buy: self.creator_min_buy,
+            lock_period: self.lock_period,
+            target_reserve_default: self.target_reserve_default,
+            paused: self.paused,
+            bump: self.bump,
+            fee_schedule: FeeSchedule::default(),
+            pause_flags: PauseFlags::default(),
+            pending_authority: None,
+            timelock_delay: crate::ADMIN_TIMELOCK_DEFAULT,
+            admin_action_nonce: 0,
+            refund_delay: crate::REFUND_DELAY_DEFAULT,
+        }
+    }
 }
 
 /// Ban list for reported bots
+/// Holds the ban authority; bans themselves live in per-wallet BanRecord PDAs
 #[account]
 pub struct BanList {
     /// Admin authority
     pub authority: Pubkey,
 
-    /// Banned accounts (we'll use a Vec for simplicity, could use a Merkle tree for scale)
+    /// Legacy bans awaiting migration to BanRecord (no longer written or checked)
     pub banned_accounts: Vec<Pubkey>,
 
     /// Bump seed
@@ -404,17 +1086,399 @@ impl BanList {
         32 + // authority
         (4 + 32 * Self::MAX_BANS) + // banned_accounts vec
         1; // bump
+}
 
-    /// Check if an account is banned
-    pub fn is_banned(&self, account: &Pubkey) -> bool {
-        self.banned_accounts.contains(account)
-    }
+/// Why a wallet was banned
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BanReason {
+    BotActivity,
+    Sniping,
+    Abuse,
+    Other,
+    /// Migrated from the legacy BanList Vec
+    Legacy,
 }
 
-/// Snapshot account (stores Merkle root for token claims)
+/// Ban of a single wallet; the wallet is banned while this PDA exists
+/// PDA: ["ban", wallet]
 #[account]
-pub struct Snapshot {
-    /// Bonding curve this snapshot belongs to
+pub struct BanRecord {
+    /// Banned wallet
+    pub wallet: Pubkey,
+
+    /// Reason code
+    pub reason: BanReason,
+
+    /// Authority that issued the ban
+    pub authority: Pubkey,
+
+    /// Timestamp of the ban
+    pub banned_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl BanRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        1 + // reason
+        32 + // authority
+        8 + // banned_at
+        1; // bump
+
+    /// A wallet is banned iff its BanRecord PDA holds a live account of this program
+    /// (callers constrain the address with seeds ["ban", wallet])
+    pub fn exists(ban_record: &AccountInfo) -> bool {
+        ban_record.owner == &crate::ID && !ban_record.data_is_empty()
+    }
+}
+
+/// Why a wallet was reported
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReportReason {
+    BotActivity,
+    Sniping,
+    Abuse,
+    Other,
+}
+
+impl ReportReason {
+    /// Ban reason recorded when the admin confirms the report
+    pub fn ban_reason(self) -> BanReason {
+        match self {
+            ReportReason::BotActivity => BanReason::BotActivity,
+            ReportReason::Sniping => BanReason::Sniping,
+            ReportReason::Abuse => BanReason::Abuse,
+            ReportReason::Other => BanReason::Other,
+        }
+    }
+}
+
+/// Review state of a bot report
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReportStatus {
+    /// Collecting reports
+    Open,
+    /// Threshold reached: buys blocked until the admin confirms or dismisses
+    Flagged,
+}
+
+/// One reporter's entry on a bot report
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ReportEntry {
+    pub reporter: Pubkey,
+    pub reason: ReportReason,
+    pub reported_at: i64,
+}
+
+impl ReportEntry {
+    pub const LEN: usize = 32 + 1 + 8;
+}
+
+/// Staked bot reports against one wallet, closed when the admin resolves them
+/// Holds the reporter stakes until then
+/// PDA: ["bot_report", reported]
+#[account]
+pub struct BotReport {
+    /// Reported wallet
+    pub reported: Pubkey,
+
+    /// Distinct reporters, in report order (first one paid the rent)
+    pub reporters: Vec<ReportEntry>,
+
+    /// Review state
+    pub status: ReportStatus,
+
+    /// When the reporter threshold was reached
+    pub flagged_at: Option<i64>,
+
+    /// Sum of reporter stakes held by this account (lamports)
+    pub total_stake: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl BotReport {
+    pub const MAX_REPORTERS: usize = 10;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // reported
+        (4 + ReportEntry::LEN * Self::MAX_REPORTERS) + // reporters vec
+        1 + // status
+        (1 + 8) + // flagged_at (Option<i64>)
+        8 + // total_stake
+        1; // bump
+
+    /// A wallet cannot buy while its BotReport exists and is flagged (sells stay open)
+    /// (callers constrain the address with seeds ["bot_report", wallet])
+    pub fn is_flagged(bot_report: &AccountInfo) -> Result<bool> {
+        if bot_report.owner != &crate::ID || bot_report.data_is_empty() {
+            return Ok(false);
+        }
+
+        let data = bot_report.try_borrow_data()?;
+        let report = BotReport::try_deserialize(&mut &data[..])?;
+        Ok(report.status == ReportStatus::Flagged)
+    }
+}
+
+/// How a curve's access list gates buys
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AccessMode {
+    /// Anyone can buy except wallets with a Deny entry
+    DenyList,
+    /// Only wallets with an Allow entry can buy (allowlist-only phase)
+    AllowList,
+}
+
+/// Per-wallet access entry kind
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AccessKind {
+    Allow,
+    Deny,
+}
+
+/// Creator-managed access controls for one curve (checked by buys, alongside global bans)
+/// No account means no restrictions
+/// PDA: ["access_list", curve]
+#[account]
+pub struct CurveAccessList {
+    /// Curve this list applies to
+    pub curve: Pubkey,
+
+    /// Current gating mode
+    pub mode: AccessMode,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl CurveAccessList {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // curve
+        1 + // mode
+        1; // bump
+
+    /// SECURITY: Check a buyer against the curve's access list
+    /// (callers constrain both addresses with their PDA seeds)
+    pub fn check_buyer(access_list: &AccountInfo, access_entry: &AccountInfo) -> Result<()> {
+        if access_list.owner != &crate::ID || access_list.data_is_empty() {
+            return Ok(());
+        }
+
+        let list_data = access_list.try_borrow_data()?;
+        let list = CurveAccessList::try_deserialize(&mut &list_data[..])?;
+
+        let kind = if access_entry.owner == &crate::ID && !access_entry.data_is_empty() {
+            let entry_data = access_entry.try_borrow_data()?;
+            Some(CurveAccessEntry::try_deserialize(&mut &entry_data[..])?.kind)
+        } else {
+            None
+        };
+
+        require!(
+            kind != Some(AccessKind::Deny),
+            crate::errors::CurveError::CurveAccessDenied
+        );
+        if list.mode == AccessMode::AllowList {
+            require!(
+                kind == Some(AccessKind::Allow),
+                crate::errors::CurveError::NotOnAllowList
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Allow or deny entry for one wallet on one curve
+/// PDA: ["access", curve, wallet]
+#[account]
+pub struct CurveAccessEntry {
+    /// Curve this entry applies to
+    pub curve: Pubkey,
+
+    /// Wallet allowed or denied
+    pub wallet: Pubkey,
+
+    /// Allow or deny
+    pub kind: AccessKind,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl CurveAccessEntry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // curve
+        32 + // wallet
+        1 + // kind
+        1; // bump
+}
+
+/// Native M-of-N multisig
+/// Its signer PDA (["multisig_signer", multisig]) is set as the authority of
+/// CurveConfig, BanList or EscrowAccount; approved proposals CPI with it as signer
+/// PDA: ["multisig", creator, multisig_id]
+#[account]
+pub struct Multisig {
+    /// Wallets allowed to propose, approve and execute
+    pub signers: Vec<Pubkey>,
+
+    /// Approvals required to execute a proposal
+    pub threshold: u8,
+
+    /// Id of the next proposal (MultisigProposal PDA seed)
+    pub nonce: u64,
+
+    /// Bumped whenever signers/threshold change, invalidating open proposals
+    pub owner_set_seqno: u32,
+
+    /// Bump of the multisig signer PDA
+    pub signer_bump: u8,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Multisig {
+    pub const MAX_SIGNERS: usize = 10;
+
+    pub const LEN: usize = 8 + // discriminator
+        (4 + 32 * Self::MAX_SIGNERS) + // signers vec
+        1 + // threshold
+        8 + // nonce
+        4 + // owner_set_seqno
+        1 + // signer_bump
+        1; // bump
+
+    /// Signers must be unique (1-10) and the threshold reachable
+    pub fn validate_signers(signers: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            !signers.is_empty()
+                && signers.len() <= Self::MAX_SIGNERS
+                && threshold > 0
+                && threshold as usize <= signers.len(),
+            crate::errors::CurveError::InvalidMultisigSigners
+        );
+
+        for (i, signer) in signers.iter().enumerate() {
+            require!(
+                !signers[..i].contains(signer),
+                crate::errors::CurveError::InvalidMultisigSigners
+            );
+        }
+        Ok(())
+    }
+
+    /// Position of a wallet in the signer set
+    pub fn signer_index(&self, key: &Pubkey) -> Result<usize> {
+        self.signers
+            .iter()
+            .position(|signer| signer == key)
+            .ok_or(error!(crate::errors::CurveError::NotMultisigSigner))
+    }
+}
+
+/// Account meta of a proposed instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl ProposalAccount {
+    pub const LEN: usize = 32 + 1 + 1;
+}
+
+/// Instruction proposed to a multisig, executed by CPI once approved
+/// PDA: ["multisig_proposal", multisig, id]
+#[account]
+pub struct MultisigProposal {
+    /// Multisig this proposal belongs to
+    pub multisig: Pubkey,
+
+    /// Sequential id (multisig.nonce at proposal time)
+    pub id: u64,
+
+    /// Program to invoke (this program or the escrow program)
+    pub program_id: Pubkey,
+
+    /// Accounts of the proposed instruction
+    pub accounts: Vec<ProposalAccount>,
+
+    /// Instruction data of the proposed instruction
+    pub data: Vec<u8>,
+
+    /// Approval per signer, parallel to multisig.signers
+    pub approvals: Vec<bool>,
+
+    /// Multisig owner set this proposal was made against
+    pub owner_set_seqno: u32,
+
+    /// Signer that proposed it
+    pub proposer: Pubkey,
+
+    /// Timestamp when proposed
+    pub created_at: i64,
+
+    /// Executed (one-shot)
+    pub executed: bool,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl MultisigProposal {
+    pub const MAX_ACCOUNTS: usize = 16;
+    pub const MAX_DATA_LEN: usize = 512;
+
+    /// Account size for a proposal with the given instruction shape
+    pub fn space(accounts: usize, data_len: usize) -> usize {
+        8 + // discriminator
+        32 + // multisig
+        8 + // id
+        32 + // program_id
+        (4 + ProposalAccount::LEN * accounts) + // accounts vec
+        (4 + data_len) + // data vec
+        (4 + Multisig::MAX_SIGNERS) + // approvals vec
+        4 + // owner_set_seqno
+        32 + // proposer
+        8 + // created_at
+        1 + // executed
+        1 // bump
+    }
+
+    /// Number of signers that approved
+    pub fn approval_count(&self) -> usize {
+        self.approvals.iter().filter(|approved| **approved).count()
+    }
+}
+
+/// Grounds on which a creator-submitted snapshot root was invalidated
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SnapshotChallenge {
+    /// Root contains a leaf whose amount differs from the holder's KeyHolder (or a non-holder)
+    AmountMismatch,
+    /// A holder's membership dispute went unanswered
+    NonMembership,
+}
+
+/// Trust level of a snapshot root
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SnapshotStatus {
+    /// Creator-submitted root: holders may challenge it until `challenge_ends_at`
+    Challengeable,
+    /// Built on-chain from KeyHolder accounts (finalize_snapshot), no challenge needed
+    Verified,
+}
+
+/// Snapshot account (stores Merkle root for token claims)
+#[account]
+pub struct Snapshot {
+    /// Bonding curve this snapshot belongs to
     pub curve: Pubkey,
 
     /// Merkle root of all key holders
@@ -434,6 +1498,18 @@ pub struct Snapshot {
 
     /// Bump seed for PDA
     pub bump: u8,
+
+    /// Whether the root can still be challenged
+    pub status: SnapshotStatus,
+
+    /// End of the challenge window (launch is blocked until then)
+    pub challenge_ends_at: i64,
+
+    /// Membership disputes awaiting an answer (launch is blocked while non-zero)
+    pub open_disputes: u32,
+
+    /// Airdrop vault PDA bump (token account owned by this snapshot)
+    pub vault_bump: u8,
 }
 
 impl Snapshot {
@@ -444,11 +1520,160 @@ impl Snapshot {
         4 + // total_holders
         8 + // total_token_pool
         8 + // created_at
+        1 + // bump
+        1 + // status
+        8 + // challenge_ends_at
+        4 + // open_disputes
+        1; // vault_bump
+    // TOTAL: 123 bytes
+
+    /// Check if challenges are accepted
+    pub fn is_challengeable(&self, current_time: i64) -> bool {
+        self.status == SnapshotStatus::Challengeable && current_time < self.challenge_ends_at
+    }
+
+    /// Check if the root is settled: verified, or unchallenged past its window
+    pub fn is_settled(&self, current_time: i64) -> bool {
+        match self.status {
+            SnapshotStatus::Verified => true,
+            SnapshotStatus::Challengeable => {
+                current_time >= self.challenge_ends_at && self.open_disputes == 0
+            }
+        }
+    }
+}
+
+/// A holder's claim that a creator-submitted root leaves them out
+/// Anyone can answer with a membership proof (stake goes to the creator);
+/// unanswered after DISPUTE_RESPONSE_PERIOD it invalidates the root (stake refunded)
+#[account]
+pub struct SnapshotDispute {
+    /// Curve whose snapshot is disputed
+    pub curve: Pubkey,
+
+    /// Holder said to be missing from the root
+    pub holder: Pubkey,
+
+    /// Wallet that opened the dispute and staked
+    pub challenger: Pubkey,
+
+    /// Root being disputed
+    pub merkle_root: [u8; 32],
+
+    /// Disputed snapshot's created_at (tells it apart from a resubmitted identical root)
+    pub snapshot_created_at: i64,
+
+    /// Timestamp when the dispute was opened
+    pub opened_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl SnapshotDispute {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // curve
+        32 + // holder
+        32 + // challenger
+        32 + // merkle_root
+        8 + // snapshot_created_at
+        8 + // opened_at
         1; // bump
-    // TOTAL: 109 bytes (pad to 128)
+
+    /// Check if this dispute targets the given snapshot (not an invalidated predecessor)
+    pub fn targets(&self, snapshot: &Snapshot) -> bool {
+        self.merkle_root == snapshot.merkle_root
+            && self.snapshot_created_at == snapshot.created_at
+    }
+}
+
+/// Snapshot leaf for a holder: keccak(owner || amount_le), as verified by claim_tokens
+pub fn snapshot_leaf(owner: &Pubkey, amount: u64) -> [u8; 32] {
+    anchor_lang::solana_program::keccak::hashv(&[owner.as_ref(), &amount.to_le_bytes()]).0
+}
+
+/// Hash two Merkle nodes in sorted order (proofs need no left/right flags)
+pub fn hash_sorted_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    anchor_lang::solana_program::keccak::hashv(&[left, right]).0
+}
+
+/// Permissionless snapshot accumulator built from a frozen curve's KeyHolder accounts
+/// Holders are appended to an incremental Merkle tree (zero-padded to DEPTH levels);
+/// finalize_snapshot checks the totals against the curve before the root is used
+#[account]
+pub struct SnapshotBuilder {
+    /// Bonding curve being snapshotted
+    pub curve: Pubkey,
+
+    /// Paid for this account (rent returned on finalize or close_snapshot_builder)
+    pub payer: Pubkey,
+
+    /// Holders registered so far (index of the next leaf)
+    pub holder_count: u32,
+
+    /// Sum of registered KeyHolder amounts
+    pub registered_supply: u128,
+
+    /// Rightmost complete subtree at each level
+    pub filled_subtrees: [[u8; 32]; SnapshotBuilder::DEPTH],
+
+    /// Root over the registered holders
+    pub root: [u8; 32],
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl SnapshotBuilder {
+    /// Tree depth (up to 2^20 holders; claim proofs carry DEPTH siblings)
+    pub const DEPTH: usize = 20;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // curve
+        32 + // payer
+        4 + // holder_count
+        16 + // registered_supply
+        32 * Self::DEPTH + // filled_subtrees
+        32 + // root
+        1; // bump
+    // TOTAL: 765 bytes
+
+    /// Append a holder's leaf, updating the root; returns the leaf index
+    pub fn insert(&mut self, owner: &Pubkey, amount: u64) -> Result<u32> {
+        let index = self.holder_count;
+        require!(
+            (index as u64) < (1u64 << Self::DEPTH),
+            crate::errors::CurveError::SnapshotTreeFull
+        );
+
+        let mut node = snapshot_leaf(owner, amount);
+        let mut zero = [0u8; 32];
+        let mut position = index;
+        for level in 0..Self::DEPTH {
+            node = if position & 1 == 0 {
+                self.filled_subtrees[level] = node;
+                hash_sorted_pair(&node, &zero)
+            } else {
+                hash_sorted_pair(&self.filled_subtrees[level], &node)
+            };
+            zero = hash_sorted_pair(&zero, &zero);
+            position /= 2;
+        }
+
+        self.root = node;
+        self.holder_count = index + 1;
+        self.registered_supply = self
+            .registered_supply
+            .checked_add(amount as u128)
+            .ok_or(crate::errors::CurveError::ArithmeticOverflow)?;
+        Ok(index)
+    }
 }
 
 /// Claim record (tracks if a user has claimed their tokens)
+/// On-chain snapshots create it when the holder is registered, which also prevents
+/// registering the same holder twice
 #[account]
 pub struct ClaimRecord {
     /// Snapshot this claim belongs to
@@ -476,3 +1701,455 @@ impl ClaimRecord {
         1; // bump
     // TOTAL: 89 bytes (pad to 128)
 }
+
+/// Token metadata for mints created by launch_with_mint
+/// PDA: ["metadata", mint]
+#[account]
+pub struct TokenMetadata {
+    /// Mint this metadata describes
+    pub mint: Pubkey,
+
+    /// Curve that launched the mint
+    pub curve: Pubkey,
+
+    /// Token name (max 32 chars)
+    pub name: String,
+
+    /// Token symbol (max 10 chars)
+    pub symbol: String,
+
+    /// Metadata JSON URI (max 200 chars)
+    pub uri: String,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl TokenMetadata {
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const MAX_SYMBOL_LEN: usize = 10;
+    pub const MAX_URI_LEN: usize = 200;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // curve
+        4 + Self::MAX_NAME_LEN + // name
+        4 + Self::MAX_SYMBOL_LEN + // symbol
+        4 + Self::MAX_URI_LEN + // uri
+        1; // bump
+    // TOTAL: 327 bytes
+
+    /// Name and symbol must be non-empty; all fields within their max lengths
+    pub fn validate(name: &str, symbol: &str, uri: &str) -> Result<()> {
+        require!(
+            !name.is_empty()
+                && !symbol.is_empty()
+                && name.len() <= Self::MAX_NAME_LEN
+                && symbol.len() <= Self::MAX_SYMBOL_LEN
+                && uri.len() <= Self::MAX_URI_LEN,
+            crate::errors::CurveError::InvalidTokenMetadata
+        );
+        Ok(())
+    }
+}
+
+/// Constant-product SOL/token pool for a token launched with launch_with_mint
+/// PDA: ["pool", curve]
+/// SOL sits in the curve's lp_vault PDA, tokens in its liquidity_vault
+#[account]
+pub struct LiquidityPool {
+    /// Curve this pool belongs to
+    pub curve: Pubkey,
+
+    /// Launched token
+    pub token_mint: Pubkey,
+
+    /// LP token mint (authority: this pool)
+    pub lp_mint: Pubkey,
+
+    /// SOL reserve (lamports, excludes lp_vault rent)
+    pub sol_reserve: u64,
+
+    /// Token reserve (base units)
+    pub token_reserve: u64,
+
+    /// Total LP supply, including locked_liquidity
+    pub lp_supply: u64,
+
+    /// Seed LP: counted in lp_supply but never minted, so it can never be withdrawn
+    pub locked_liquidity: u64,
+
+    /// Creation timestamp
+    pub created_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Bump of the curve's lp_vault PDA (SOL side)
+    pub sol_vault_bump: u8,
+
+    /// Bump of the curve's liquidity_vault PDA (token side)
+    pub token_vault_bump: u8,
+}
+
+impl LiquidityPool {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // curve
+        32 + // token_mint
+        32 + // lp_mint
+        8 + // sol_reserve
+        8 + // token_reserve
+        8 + // lp_supply
+        8 + // locked_liquidity
+        8 + // created_at
+        1 + // bump
+        1 + // sol_vault_bump
+        1; // token_vault_bump
+    // TOTAL: 147 bytes
+}
+
+/// Fee breakdown of a quoted trade
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub enum QuoteFees {
+    Buy(V6BuyFeeDistribution),
+    Sell(V6SellFeeDistribution),
+}
+
+/// Quote returned via return data by the `quote` instruction
+/// Clients simulate the instruction to get exact on-chain numbers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TradeQuote {
+    /// Side that was quoted
+    pub side: TradeSide,
+
+    /// Number of keys quoted
+    pub amount: u64,
+
+    /// Gross cost (buy) or gross return before fees (sell), in lamports
+    pub gross: u128,
+
+    /// Full V6 fee split for the chosen referrer type
+    pub fees: QuoteFees,
+
+    /// Keys the holder can still buy under the per-wallet cap
+    pub remaining_key_cap: u64,
+
+    /// Spot price of the next key after the trade, in lamports
+    pub spot_price_after: u128,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// All-zero account body (Options None, enums at their first variant)
+    fn zeroed<T: AnchorDeserialize>(len: usize) -> T {
+        T::deserialize(&mut &vec![0u8; len][..]).unwrap()
+    }
+
+    /// Serialized account data, discriminator included
+    fn account_data<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn fee_vault() -> FeeVault {
+        FeeVault {
+            curve: Pubkey::new_unique(),
+            creator_owed: 0,
+            treasury_owed: 0,
+            buyback_owed: 0,
+            community_owed: 0,
+            referral_owed: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_fee_vault_accrue_and_take_owed() {
+        let mut vault = fee_vault();
+
+        assert_eq!(vault.accrue(1, 2, 3, 4, 5).unwrap(), 15);
+        assert_eq!(vault.accrue(10, 20, 30, 40, 50).unwrap(), 150);
+
+        assert_eq!(vault.take_owed(FeeRecipient::Creator), 11);
+        assert_eq!(vault.take_owed(FeeRecipient::Treasury), 22);
+        assert_eq!(vault.take_owed(FeeRecipient::Buyback), 33);
+        assert_eq!(vault.take_owed(FeeRecipient::Community), 44);
+        assert_eq!(vault.take_owed(FeeRecipient::Referrer), 55);
+
+        // Ledgers are zeroed once taken
+        assert_eq!(vault.take_owed(FeeRecipient::Treasury), 0);
+        assert_eq!(vault.creator_owed + vault.referral_owed, 0);
+    }
+
+    #[test]
+    fn test_fee_vault_accrue_overflow() {
+        let mut vault = fee_vault();
+        vault.treasury_owed = u128::MAX;
+
+        assert!(vault.accrue(0, 1, 0, 0, 0).is_err());
+        assert!(fee_vault().accrue(u128::MAX, 0, 0, 0, 1).is_err());
+    }
+
+    fn snapshot_builder() -> SnapshotBuilder {
+        SnapshotBuilder {
+            curve: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            holder_count: 0,
+            registered_supply: 0,
+            filled_subtrees: [[0u8; 32]; SnapshotBuilder::DEPTH],
+            root: [0u8; 32],
+            bump: 255,
+        }
+    }
+
+    /// DEPTH siblings for `index`, from a full zero-padded tree over `leaves`
+    fn merkle_proof(leaves: &[[u8; 32]], index: usize) -> Vec<[u8; 32]> {
+        let mut level = leaves.to_vec();
+        let mut zero = [0u8; 32];
+        let mut position = index;
+        let mut proof = Vec::with_capacity(SnapshotBuilder::DEPTH);
+
+        for _ in 0..SnapshotBuilder::DEPTH {
+            proof.push(level.get(position ^ 1).copied().unwrap_or(zero));
+            level = level
+                .chunks(2)
+                .map(|pair| hash_sorted_pair(&pair[0], pair.get(1).unwrap_or(&zero)))
+                .collect();
+            zero = hash_sorted_pair(&zero, &zero);
+            position /= 2;
+        }
+        proof
+    }
+
+    #[test]
+    fn test_snapshot_builder_proofs_verify() {
+        let mut builder = snapshot_builder();
+        let holders: Vec<(Pubkey, u64)> = (1..=13u64)
+            .map(|amount| (Pubkey::new_unique(), amount * 1_000))
+            .collect();
+
+        for (index, (owner, amount)) in holders.iter().enumerate() {
+            assert_eq!(builder.insert(owner, *amount).unwrap(), index as u32);
+        }
+        assert_eq!(builder.holder_count, 13);
+        assert_eq!(builder.registered_supply, 91_000);
+
+        let leaves: Vec<[u8; 32]> = holders
+            .iter()
+            .map(|(owner, amount)| snapshot_leaf(owner, *amount))
+            .collect();
+
+        for (index, (owner, amount)) in holders.iter().enumerate() {
+            let proof = merkle_proof(&leaves, index);
+            assert_eq!(proof.len(), SnapshotBuilder::DEPTH);
+            assert!(crate::verify_merkle_proof(&proof, &builder.root, &leaves[index]));
+
+            // A wrong amount does not verify against the same proof
+            let forged = snapshot_leaf(owner, amount + 1);
+            assert!(!crate::verify_merkle_proof(&proof, &builder.root, &forged));
+        }
+    }
+
+    #[test]
+    fn test_pause_flags_checks() {
+        let mut config: CurveConfig = zeroed(CurveConfig::LEN);
+        assert!(config.require_trading_enabled().is_ok());
+        assert!(config.require_creation_enabled().is_ok());
+        assert!(config.require_launches_enabled().is_ok());
+        assert!(config.require_claims_enabled().is_ok());
+
+        // Each flag only blocks its own group
+        config.pause_flags.trading = true;
+        assert!(config.require_trading_enabled().is_err());
+        assert!(config.require_creation_enabled().is_ok());
+        assert!(config.require_claims_enabled().is_ok());
+
+        config.pause_flags = PauseFlags { claims: true, ..PauseFlags::default() };
+        assert!(config.require_claims_enabled().is_err());
+        assert!(config.require_trading_enabled().is_ok());
+        assert!(config.require_launches_enabled().is_ok());
+
+        // The global switch blocks everything
+        config.pause_flags = PauseFlags::default();
+        config.paused = true;
+        assert!(config.require_trading_enabled().is_err());
+        assert!(config.require_creation_enabled().is_err());
+        assert!(config.require_launches_enabled().is_err());
+        assert!(config.require_claims_enabled().is_err());
+
+        // Per-curve admin pause
+        let mut curve: BondingCurve = zeroed(BondingCurve::LEN);
+        assert!(curve.require_not_paused().is_ok());
+        curve.admin_paused = true;
+        assert!(curve.require_not_paused().is_err());
+    }
+
+    #[test]
+    fn test_multisig_validate_signers() {
+        let signers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+        assert!(Multisig::validate_signers(&signers, 1).is_ok());
+        assert!(Multisig::validate_signers(&signers, 3).is_ok());
+
+        // Threshold must be reachable and non-zero
+        assert!(Multisig::validate_signers(&signers, 0).is_err());
+        assert!(Multisig::validate_signers(&signers, 4).is_err());
+
+        // 1..=MAX_SIGNERS signers
+        assert!(Multisig::validate_signers(&[], 1).is_err());
+        let too_many: Vec<Pubkey> = (0..Multisig::MAX_SIGNERS + 1)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        assert!(Multisig::validate_signers(&too_many, 1).is_err());
+        assert!(Multisig::validate_signers(&too_many[..Multisig::MAX_SIGNERS], 1).is_ok());
+
+        // No duplicates
+        let duplicated = [signers[0], signers[1], signers[0]];
+        assert!(Multisig::validate_signers(&duplicated, 2).is_err());
+    }
+
+    #[test]
+    fn test_bot_report_is_flagged() {
+        let key = Pubkey::new_unique();
+        let mut report = BotReport {
+            reported: Pubkey::new_unique(),
+            reporters: vec![ReportEntry {
+                reporter: Pubkey::new_unique(),
+                reason: ReportReason::BotActivity,
+                reported_at: 1,
+            }],
+            status: ReportStatus::Open,
+            flagged_at: None,
+            total_stake: 0,
+            bump: 255,
+        };
+
+        // No report account
+        let (mut lamports, mut data) = (0, Vec::new());
+        let system = anchor_lang::system_program::ID;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &system, false, 0);
+        assert!(!BotReport::is_flagged(&info).unwrap());
+
+        // Open report
+        let (mut lamports, mut data) = (1, account_data(&report));
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &crate::ID, false, 0);
+        assert!(!BotReport::is_flagged(&info).unwrap());
+
+        // Flagged report
+        report.status = ReportStatus::Flagged;
+        report.flagged_at = Some(2);
+        let (mut lamports, mut data) = (1, account_data(&report));
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &crate::ID, false, 0);
+        assert!(BotReport::is_flagged(&info).unwrap());
+
+        // Same bytes owned by another program are ignored
+        let other = Pubkey::new_unique();
+        let (mut lamports, mut data) = (1, account_data(&report));
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &other, false, 0);
+        assert!(!BotReport::is_flagged(&info).unwrap());
+    }
+
+    #[test]
+    fn test_curve_access_list_check_buyer() {
+        let curve = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+        let (list_key, entry_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let system = anchor_lang::system_program::ID;
+
+        let list = |mode| account_data(&CurveAccessList { curve, mode, bump: 255 });
+        let entry = |kind| account_data(&CurveAccessEntry { curve, wallet: buyer, kind, bump: 255 });
+
+        // Runs check_buyer with optional list/entry accounts (None = account does not exist)
+        let check = |list_data: Option<Vec<u8>>, entry_data: Option<Vec<u8>>| {
+            let (mut list_lamports, mut entry_lamports) = (1, 1);
+            let list_owner = if list_data.is_some() { crate::ID } else { system };
+            let entry_owner = if entry_data.is_some() { crate::ID } else { system };
+            let mut list_data = list_data.unwrap_or_default();
+            let mut entry_data = entry_data.unwrap_or_default();
+            let list_info = AccountInfo::new(
+                &list_key, false, false, &mut list_lamports, &mut list_data, &list_owner, false, 0,
+            );
+            let entry_info = AccountInfo::new(
+                &entry_key, false, false, &mut entry_lamports, &mut entry_data, &entry_owner, false, 0,
+            );
+            CurveAccessList::check_buyer(&list_info, &entry_info)
+        };
+
+        // No list: no restrictions, even for a leftover deny entry
+        assert!(check(None, None).is_ok());
+        assert!(check(None, Some(entry(AccessKind::Deny))).is_ok());
+
+        // Deny list: only deny entries are blocked
+        assert!(check(Some(list(AccessMode::DenyList)), None).is_ok());
+        assert!(check(Some(list(AccessMode::DenyList)), Some(entry(AccessKind::Allow))).is_ok());
+        assert!(check(Some(list(AccessMode::DenyList)), Some(entry(AccessKind::Deny))).is_err());
+
+        // Allow list: only allow entries pass
+        assert!(check(Some(list(AccessMode::AllowList)), None).is_err());
+        assert!(check(Some(list(AccessMode::AllowList)), Some(entry(AccessKind::Allow))).is_ok());
+        assert!(check(Some(list(AccessMode::AllowList)), Some(entry(AccessKind::Deny))).is_err());
+    }
+
+    #[test]
+    fn test_legacy_bonding_curve_migrate() {
+        let mut legacy: LegacyBondingCurve = zeroed(BondingCurve::LEN);
+        legacy.creator = Pubkey::new_unique();
+        legacy.twitter_handle = "legacy_handle".to_string();
+        legacy.status = CurveStatus::Frozen;
+        legacy.supply = 42;
+        legacy.reserve_balance = 7_000_000_000;
+        legacy.freeze_ts = Some(1_700_000_000);
+        legacy.freeze_trigger = Some("reserve_threshold".to_string());
+        legacy.supply_at_freeze = 42;
+        legacy.bump = 254;
+
+        // migrate_curve reads the legacy body from the raw account bytes
+        let bytes = legacy.try_to_vec().unwrap();
+        let legacy = LegacyBondingCurve::deserialize(&mut &bytes[..]).unwrap();
+        let curve = legacy.migrate().unwrap();
+
+        assert_eq!(curve.twitter_handle, "legacy_handle");
+        assert_eq!(curve.status, CurveStatus::Frozen);
+        assert_eq!(curve.supply, 42);
+        assert_eq!(curve.reserve_balance, 7_000_000_000);
+        assert_eq!(curve.freeze_ts, Some(1_700_000_000));
+        assert_eq!(curve.freeze_trigger, Some(FreezeTrigger::ReserveThreshold));
+        assert_eq!(curve.bump, 254);
+        assert_eq!(curve.fee_schedule, FeeSchedule::default());
+        assert!(curve.fee_schedule.validate().is_ok());
+        assert!(!curve.admin_paused);
+        assert!(account_data(&curve).len() <= BondingCurve::LEN);
+
+        // Unfrozen curves have no trigger; unknown strings are rejected
+        let mut legacy: LegacyBondingCurve = zeroed(BondingCurve::LEN);
+        assert_eq!(legacy.migrate().unwrap().freeze_trigger, None);
+
+        legacy = zeroed(BondingCurve::LEN);
+        legacy.freeze_trigger = Some("sideways".to_string());
+        assert!(legacy.migrate().is_err());
+    }
+
+    #[test]
+    fn test_calculate_refund() {
+        let mut curve: BondingCurve = zeroed(BondingCurve::LEN);
+
+        // Nothing was frozen
+        assert!(curve.calculate_refund(1).is_err());
+
+        curve.supply_at_freeze = 3;
+        curve.reserve_at_freeze = 10_000_000_000; // 10 SOL
+
+        // Pro-rata, rounded down
+        assert_eq!(curve.calculate_refund(1).unwrap(), 3_333_333_333);
+        assert_eq!(curve.calculate_refund(2).unwrap(), 6_666_666_666);
+        assert_eq!(curve.calculate_refund(0).unwrap(), 0);
+
+        // Claims in any order never exceed the reserve
+        let total: u128 = [1u64, 1, 1].iter().map(|keys| curve.calculate_refund(*keys).unwrap()).sum();
+        assert!(total <= curve.reserve_at_freeze);
+        assert_eq!(curve.calculate_refund(3).unwrap(), curve.reserve_at_freeze);
+    }
+}
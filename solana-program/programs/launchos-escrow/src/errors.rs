@@ -28,4 +28,7 @@ pub enum EscrowError {
 
     #[msg("Mathematical overflow occurred")]
     MathOverflow,
+
+    #[msg("Signer is not the pending escrow authority")]
+    NotPendingAuthority,
 }
@@ -173,6 +173,38 @@ pub mod launchos_escrow {
         msg!("Escrow system UNPAUSED");
         Ok(())
     }
+
+    /// Propose a new escrow authority (step 1 of 2, authority only)
+    /// e.g. the signer PDA of a launchos-curve multisig
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let pending = &mut ctx.accounts.pending;
+        pending.pending_authority = new_authority;
+        pending.payer = ctx.accounts.payer.key();
+        pending.proposed_at = Clock::get()?.unix_timestamp;
+        pending.bump = ctx.bumps.pending;
+
+        msg!("Escrow authority proposed: {}", new_authority);
+        Ok(())
+    }
+
+    /// Cancel a pending authority proposal (authority only)
+    pub fn cancel_authority_proposal(ctx: Context<CancelAuthorityProposal>) -> Result<()> {
+        msg!(
+            "Escrow authority proposal cancelled: {}",
+            ctx.accounts.pending.pending_authority
+        );
+        Ok(())
+    }
+
+    /// Accept the escrow authority (step 2 of 2, signed by the proposed authority)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        let old_authority = escrow.authority;
+        escrow.authority = ctx.accounts.new_authority.key();
+
+        msg!("Escrow authority transferred: {} -> {}", old_authority, escrow.authority);
+        Ok(())
+    }
 }
 
 // ============================================================================
@@ -308,3 +340,82 @@ pub struct UpdateEscrow<'info> {
     #[account(constraint = authority.key() == escrow.authority @ EscrowError::Unauthorized)]
     pub authority: Signer<'info>,
 }
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        seeds = [b"escrow"],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, EscrowAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PendingAuthority::INIT_SPACE,
+        seeds = [b"pending_authority"],
+        bump
+    )]
+    pub pending: Account<'info, PendingAuthority>,
+
+    /// CHECK: Authority validated by escrow account
+    #[account(constraint = authority.key() == escrow.authority @ EscrowError::Unauthorized)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityProposal<'info> {
+    #[account(
+        seeds = [b"escrow"],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, EscrowAccount>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"pending_authority"],
+        bump = pending.bump,
+        has_one = payer
+    )]
+    pub pending: Account<'info, PendingAuthority>,
+
+    /// CHECK: Authority validated by escrow account
+    #[account(constraint = authority.key() == escrow.authority @ EscrowError::Unauthorized)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Rent refund destination, checked against pending.payer
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow"],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, EscrowAccount>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"pending_authority"],
+        bump = pending.bump,
+        has_one = payer,
+        constraint = pending.pending_authority == new_authority.key() @ EscrowError::NotPendingAuthority
+    )]
+    pub pending: Account<'info, PendingAuthority>,
+
+    pub new_authority: Signer<'info>,
+
+    /// CHECK: Rent refund destination, checked against pending.payer
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
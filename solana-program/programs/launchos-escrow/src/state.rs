@@ -16,6 +16,22 @@ pub struct EscrowAccount {
     pub bump: u8,
 }
 
+/// Authority handover awaiting acceptance (step 1 of 2)
+/// Kept outside EscrowAccount so the deployed escrow layout is unchanged
+/// PDA: ["pending_authority"]
+#[account]
+#[derive(InitSpace)]
+pub struct PendingAuthority {
+    /// Authority that must sign accept_authority
+    pub pending_authority: Pubkey,
+    /// Account refunded when the proposal is accepted or cancelled
+    pub payer: Pubkey,
+    /// Timestamp when the handover was proposed
+    pub proposed_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
 /// Individual escrow pool for a specific use case
 #[account]
 #[derive(InitSpace)]
@@ -0,0 +1,499 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LaunchosCurve } from "../target/types/launchos_curve";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("launchos-curve launch", () => {
+  // Configure the client to use the local cluster
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.LaunchosCurve as Program<LaunchosCurve>;
+
+  // Mirrors TOKEN_TOTAL_SUPPLY and LAUNCH_LIQUIDITY_BPS
+  const TOKEN_TOTAL_SUPPLY = new anchor.BN("1000000000000000");
+  const LIQUIDITY_AMOUNT = TOKEN_TOTAL_SUPPLY.muln(2000).divn(10000);
+  const AIRDROP_AMOUNT = TOKEN_TOTAL_SUPPLY.sub(LIQUIDITY_AMOUNT);
+
+  // Test accounts
+  let creator: Keypair;
+  let marketingWallet: Keypair;
+  let utilityWallet: Keypair;
+  let configPda: PublicKey;
+  let curvePda: PublicKey;
+  let reserveVaultPda: PublicKey;
+  let feeVaultPda: PublicKey;
+  let holderPda: PublicKey;
+  let snapshotPda: PublicKey;
+  let lpVaultPda: PublicKey;
+  let tokenMintPda: PublicKey;
+  let metadataPda: PublicKey;
+  let airdropVaultPda: PublicKey;
+  let liquidityVaultPda: PublicKey;
+
+  const twitterHandle = `launch_${Date.now()}`;
+
+  const pda = (...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const chainTime = async () =>
+    provider.connection.getBlockTime(await provider.connection.getSlot());
+
+  before(async () => {
+    creator = Keypair.generate();
+    marketingWallet = Keypair.generate();
+    utilityWallet = Keypair.generate();
+
+    await provider.connection.requestAirdrop(
+      creator.publicKey,
+      10 * anchor.web3.LAMPORTS_PER_SOL
+    );
+
+    // Wait for airdrop to confirm
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    // Derive PDAs
+    configPda = pda(Buffer.from("config"));
+    curvePda = pda(Buffer.from("curve"), Buffer.from(twitterHandle));
+    reserveVaultPda = pda(Buffer.from("reserve"), curvePda.toBuffer());
+    feeVaultPda = pda(Buffer.from("fee_vault"), curvePda.toBuffer());
+    holderPda = pda(Buffer.from("holder"), curvePda.toBuffer(), creator.publicKey.toBuffer());
+    snapshotPda = pda(Buffer.from("snapshot"), curvePda.toBuffer());
+    lpVaultPda = pda(Buffer.from("lp_vault"), curvePda.toBuffer());
+    tokenMintPda = pda(Buffer.from("mint"), curvePda.toBuffer());
+    metadataPda = pda(Buffer.from("metadata"), tokenMintPda.toBuffer());
+    airdropVaultPda = pda(Buffer.from("airdrop_vault"), snapshotPda.toBuffer());
+    liquidityVaultPda = pda(Buffer.from("liquidity_vault"), curvePda.toBuffer());
+
+    // The config is a singleton, another suite may have created it
+    if ((await provider.connection.getAccountInfo(configPda)) === null) {
+      await program.methods
+        .initialize(
+          provider.wallet.publicKey,
+          provider.wallet.publicKey,
+          provider.wallet.publicKey
+        )
+        .accountsPartial({
+          config: configPda,
+          authority: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    // Create, buy in and activate with a time-based freeze a few seconds out
+    const launchTs = new anchor.BN((await chainTime()) + 3);
+
+    await program.methods
+      .createCurve(twitterHandle, { profile: {} }, launchTs)
+      .accountsPartial({
+        curve: curvePda,
+        reserveVault: reserveVaultPda,
+        feeVault: feeVaultPda,
+        creator: creator.publicKey,
+        config: configPda,
+        banRecord: pda(Buffer.from("ban"), creator.publicKey.toBuffer()),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .creatorInitialBuy(new anchor.BN(10))
+      .accountsPartial({
+        curve: curvePda,
+        reserveVault: reserveVaultPda,
+        keyHolder: holderPda,
+        buyer: creator.publicKey,
+        feeVault: feeVaultPda,
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .activateCurve()
+      .accountsPartial({
+        curve: curvePda,
+        creator: creator.publicKey,
+        config: configPda,
+      })
+      .signers([creator])
+      .rpc();
+
+    // Wait for launch_ts, then freeze via the permissionless crank (no keeper pool)
+    while ((await chainTime()) < launchTs.toNumber()) {
+      await new Promise(resolve => setTimeout(resolve, 500));
+    }
+
+    await program.methods
+      .crankFreeze()
+      .accountsPartial({
+        curve: curvePda,
+        keeperPool: null,
+        keeper: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    // On-chain snapshot: verified as soon as every holder is registered
+    const builderPda = pda(Buffer.from("snapshot_builder"), curvePda.toBuffer());
+
+    await program.methods
+      .beginSnapshot()
+      .accountsPartial({
+        curve: curvePda,
+        builder: builderPda,
+        payer: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .registerSnapshotHolder()
+      .accountsPartial({
+        curve: curvePda,
+        builder: builderPda,
+        keyHolder: holderPda,
+        snapshot: snapshotPda,
+        claimRecord: pda(Buffer.from("claim"), snapshotPda.toBuffer(), creator.publicKey.toBuffer()),
+        payer: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .finalizeSnapshot()
+      .accountsPartial({
+        curve: curvePda,
+        builder: builderPda,
+        snapshot: snapshotPda,
+        builderPayer: provider.wallet.publicKey,
+        payer: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const curveAccount = await program.account.bondingCurve.fetch(curvePda);
+    assert.deepEqual(curveAccount.status, { frozen: {} }, "Curve should be frozen");
+    assert.isNotNull(curveAccount.snapshotRoot, "Snapshot root should be set");
+  });
+
+  it("Launches with an on-chain mint and splits the fixed supply", async () => {
+    const tx = await program.methods
+      .launchWithMint(25, "Launch Test", "LNCH", "https://example.com/lnch.json")
+      .accountsPartial({
+        curve: curvePda,
+        reserveVault: reserveVaultPda,
+        lpVault: lpVaultPda,
+        marketingWallet: marketingWallet.publicKey,
+        utilityWallet: utilityWallet.publicKey,
+        creator: creator.publicKey,
+        config: configPda,
+        snapshot: snapshotPda,
+        tokenMint: tokenMintPda,
+        metadata: metadataPda,
+        airdropVault: airdropVaultPda,
+        liquidityVault: liquidityVaultPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    console.log("Launch with mint transaction signature:", tx);
+
+    // Fixed supply, minted once
+    const mint = await getMint(provider.connection, tokenMintPda);
+    assert.equal(mint.decimals, 6, "Mint should have 6 decimals");
+    assert.equal(mint.supply.toString(), TOKEN_TOTAL_SUPPLY.toString(), "Supply should be fixed");
+
+    // 80% to the snapshot's airdrop vault, 20% to the curve's liquidity vault
+    const airdropVault = await getAccount(provider.connection, airdropVaultPda);
+    const liquidityVault = await getAccount(provider.connection, liquidityVaultPda);
+    assert.equal(airdropVault.amount.toString(), AIRDROP_AMOUNT.toString());
+    assert.equal(liquidityVault.amount.toString(), LIQUIDITY_AMOUNT.toString());
+    assert.equal(airdropVault.owner.toString(), snapshotPda.toString());
+    assert.equal(liquidityVault.owner.toString(), curvePda.toString());
+
+    const snapshot = await program.account.snapshot.fetch(snapshotPda);
+    assert.equal(snapshot.totalTokenPool.toString(), AIRDROP_AMOUNT.toString());
+
+    const curveAccount = await program.account.bondingCurve.fetch(curvePda);
+    assert.deepEqual(curveAccount.status, { launched: {} }, "Curve should be launched");
+    assert.equal(curveAccount.tokenMint.toString(), tokenMintPda.toString());
+
+    const metadata = await program.account.tokenMetadata.fetch(metadataPda);
+    assert.equal(metadata.mint.toString(), tokenMintPda.toString());
+    assert.equal(metadata.name, "Launch Test");
+    assert.equal(metadata.symbol, "LNCH");
+  });
+
+  it("Revokes the mint authority after minting", async () => {
+    const mint = await getMint(provider.connection, tokenMintPda);
+    assert.isNull(mint.mintAuthority, "Mint authority should be revoked");
+    assert.isNull(mint.freezeAuthority, "Mint should have no freeze authority");
+  });
+
+  describe("liquidity pool", () => {
+    let trader: Keypair;
+    let poolPda: PublicKey;
+    let lpMintPda: PublicKey;
+    let traderTokenAccount: PublicKey;
+    let traderLpAccount: PublicKey;
+
+    // Floor square root, mirrors amm::isqrt
+    const isqrt = (n: anchor.BN): anchor.BN => {
+      if (n.ltn(2)) return n;
+      let x = n;
+      let y = n.addn(1).shrn(1);
+      while (y.lt(x)) {
+        x = y;
+        y = x.add(n.div(x)).shrn(1);
+      }
+      return x;
+    };
+
+    // Mirrors amm::calculate_swap_output (0.3% fee kept in the pool)
+    const swapOutput = (amountIn: anchor.BN, reserveIn: anchor.BN, reserveOut: anchor.BN) => {
+      const inWithFee = amountIn.muln(9970);
+      return inWithFee.mul(reserveOut).div(reserveIn.muln(10000).add(inWithFee));
+    };
+
+    const tokenBalance = async (account: PublicKey) =>
+      new anchor.BN((await getAccount(provider.connection, account)).amount.toString());
+
+    const swap = (amountIn: anchor.BN, minAmountOut: anchor.BN, solToToken: boolean) =>
+      program.methods
+        .swap(amountIn, minAmountOut, solToToken, null)
+        .accountsPartial({
+          curve: curvePda,
+          pool: poolPda,
+          lpVault: lpVaultPda,
+          liquidityVault: liquidityVaultPda,
+          tokenMint: tokenMintPda,
+          userTokenAccount: traderTokenAccount,
+          user: trader.publicKey,
+          config: configPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([trader])
+        .rpc();
+
+    const addLiquidity = (solAmount: anchor.BN, maxTokenAmount: anchor.BN) =>
+      program.methods
+        .addLiquidity(solAmount, maxTokenAmount, null)
+        .accountsPartial({
+          curve: curvePda,
+          pool: poolPda,
+          lpVault: lpVaultPda,
+          liquidityVault: liquidityVaultPda,
+          lpMint: lpMintPda,
+          userTokenAccount: traderTokenAccount,
+          userLpAccount: traderLpAccount,
+          user: trader.publicKey,
+          config: configPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([trader])
+        .rpc();
+
+    const removeLiquidity = (lpAmount: anchor.BN, minSol: anchor.BN, minToken: anchor.BN) =>
+      program.methods
+        .removeLiquidity(lpAmount, minSol, minToken, null)
+        .accountsPartial({
+          curve: curvePda,
+          pool: poolPda,
+          lpVault: lpVaultPda,
+          liquidityVault: liquidityVaultPda,
+          lpMint: lpMintPda,
+          userLpAccount: traderLpAccount,
+          tokenMint: tokenMintPda,
+          userTokenAccount: traderTokenAccount,
+          user: trader.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([trader])
+        .rpc();
+
+    before(async () => {
+      trader = Keypair.generate();
+
+      await provider.connection.requestAirdrop(
+        trader.publicKey,
+        5 * anchor.web3.LAMPORTS_PER_SOL
+      );
+
+      // Wait for airdrop to confirm
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      poolPda = pda(Buffer.from("pool"), curvePda.toBuffer());
+      lpMintPda = pda(Buffer.from("lp_mint"), poolPda.toBuffer());
+      traderTokenAccount = getAssociatedTokenAddressSync(tokenMintPda, trader.publicKey);
+      traderLpAccount = getAssociatedTokenAddressSync(lpMintPda, trader.publicKey);
+    });
+
+    it("Seeds the pool from lp_vault and liquidity_vault", async () => {
+      await program.methods
+        .createPool()
+        .accountsPartial({
+          curve: curvePda,
+          pool: poolPda,
+          lpVault: lpVaultPda,
+          tokenMint: tokenMintPda,
+          liquidityVault: liquidityVaultPda,
+          lpMint: lpMintPda,
+          payer: provider.wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const curveAccount = await program.account.bondingCurve.fetch(curvePda);
+      const pool = await program.account.liquidityPool.fetch(poolPda);
+
+      // SOL side is the launch's initial buy, held in lp_vault above its rent
+      assert.equal(pool.solReserve.toString(), curveAccount.initialBuyAmount.toString());
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(8);
+      const lpVaultBalance = await provider.connection.getBalance(lpVaultPda);
+      assert.equal(lpVaultBalance, rent + pool.solReserve.toNumber());
+
+      // Token side is the whole liquidity vault
+      assert.equal(pool.tokenReserve.toString(), LIQUIDITY_AMOUNT.toString());
+
+      // Seed LP is counted but never minted, so it can never be withdrawn
+      const seedLiquidity = isqrt(pool.solReserve.mul(pool.tokenReserve));
+      assert.equal(pool.lockedLiquidity.toString(), seedLiquidity.toString());
+      assert.equal(pool.lpSupply.toString(), seedLiquidity.toString());
+      const lpMint = await getMint(provider.connection, lpMintPda);
+      assert.equal(lpMint.supply.toString(), "0", "Seed LP should not be minted");
+      assert.equal(lpMint.mintAuthority.toString(), poolPda.toString());
+    });
+
+    it("Enforces swap slippage", async () => {
+      const amountIn = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 100); // 0.01 SOL
+      let pool = await program.account.liquidityPool.fetch(poolPda);
+      const expectedOut = swapOutput(amountIn, pool.solReserve, pool.tokenReserve);
+
+      try {
+        await swap(amountIn, expectedOut.addn(1), true);
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(
+          error.toString(),
+          "PoolSlippageExceeded",
+          "Should throw PoolSlippageExceeded error"
+        );
+      }
+
+      await swap(amountIn, expectedOut, true);
+
+      assert.equal((await tokenBalance(traderTokenAccount)).toString(), expectedOut.toString());
+
+      const solBefore = pool.solReserve;
+      pool = await program.account.liquidityPool.fetch(poolPda);
+      assert.equal(pool.solReserve.toString(), solBefore.add(amountIn).toString());
+      assert.equal(
+        pool.tokenReserve.toString(),
+        LIQUIDITY_AMOUNT.sub(expectedOut).toString()
+      );
+
+      // And back: token -> SOL with the same slippage check
+      const tokensIn = expectedOut.divn(2);
+      const expectedSol = swapOutput(tokensIn, pool.tokenReserve, pool.solReserve);
+
+      try {
+        await swap(tokensIn, expectedSol.addn(1), false);
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "PoolSlippageExceeded");
+      }
+
+      const lamportsBefore = await provider.connection.getBalance(trader.publicKey);
+      await swap(tokensIn, expectedSol, false);
+      const lamportsAfter = await provider.connection.getBalance(trader.publicKey);
+
+      // Received expectedSol, minus the transaction fee
+      assert.isAbove(lamportsAfter, lamportsBefore + expectedSol.toNumber() - 10_000);
+    });
+
+    it("Adds and removes liquidity, leaving the seed LP locked", async () => {
+      const solAmount = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 1000); // 0.001 SOL
+      let pool = await program.account.liquidityPool.fetch(poolPda);
+
+      // Token side rounds up, LP rounds down (amm::calculate_deposit)
+      const numerator = solAmount.mul(pool.tokenReserve);
+      const tokenAmount = numerator.add(pool.solReserve).subn(1).div(pool.solReserve);
+      const lpAmount = solAmount.mul(pool.lpSupply).div(pool.solReserve);
+
+      try {
+        await addLiquidity(solAmount, tokenAmount.subn(1));
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "PoolSlippageExceeded");
+      }
+
+      const tokensBefore = await tokenBalance(traderTokenAccount);
+      await addLiquidity(solAmount, tokenAmount);
+
+      assert.equal((await tokenBalance(traderLpAccount)).toString(), lpAmount.toString());
+      assert.equal(
+        (await tokenBalance(traderTokenAccount)).toString(),
+        tokensBefore.sub(tokenAmount).toString()
+      );
+
+      pool = await program.account.liquidityPool.fetch(poolPda);
+      assert.equal(pool.lpSupply.toString(), pool.lockedLiquidity.add(lpAmount).toString());
+
+      // Remove everything the trader minted
+      const [expectedSol, expectedTokens] = [
+        lpAmount.mul(pool.solReserve).div(pool.lpSupply),
+        lpAmount.mul(pool.tokenReserve).div(pool.lpSupply),
+      ];
+
+      try {
+        await removeLiquidity(lpAmount, expectedSol.addn(1), expectedTokens);
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "PoolSlippageExceeded");
+      }
+
+      await removeLiquidity(lpAmount, expectedSol, expectedTokens);
+
+      assert.equal((await tokenBalance(traderLpAccount)).toString(), "0");
+
+      pool = await program.account.liquidityPool.fetch(poolPda);
+      assert.equal(
+        pool.lpSupply.toString(),
+        pool.lockedLiquidity.toString(),
+        "Only the locked seed LP should remain"
+      );
+      assert.isTrue(pool.solReserve.gtn(0) && pool.tokenReserve.gtn(0));
+
+      const lpMint = await getMint(provider.connection, lpMintPda);
+      assert.equal(lpMint.supply.toString(), "0", "No LP tokens should be outstanding");
+
+      // The locked seed LP has no tokens behind it, so nobody can burn it
+      try {
+        await removeLiquidity(new anchor.BN(1), new anchor.BN(0), new anchor.BN(0));
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "0x1", "Should fail with insufficient LP funds");
+      }
+    });
+  });
+});
@@ -0,0 +1,261 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LaunchosCurve } from "../target/types/launchos_curve";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+
+describe("launchos-curve refund", () => {
+  // Configure the client to use the local cluster
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.LaunchosCurve as Program<LaunchosCurve>;
+
+  // Test accounts
+  let creator: Keypair;
+  let holder: Keypair;
+  let configPda: PublicKey;
+  let curvePda: PublicKey;
+  let reserveVaultPda: PublicKey;
+  let feeVaultPda: PublicKey;
+
+  const twitterHandle = `refund_${Date.now()}`;
+
+  const pda = (...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const chainTime = async () =>
+    provider.connection.getBlockTime(await provider.connection.getSlot());
+
+  const holderPda = (wallet: PublicKey) =>
+    pda(Buffer.from("holder"), curvePda.toBuffer(), wallet.toBuffer());
+
+  const startRefund = (caller: Keypair | null) =>
+    program.methods
+      .startRefund()
+      .accountsPartial({
+        curve: curvePda,
+        config: configPda,
+        caller: caller ? caller.publicKey : provider.wallet.publicKey,
+      })
+      .signers(caller ? [caller] : [])
+      .rpc();
+
+  const claimRefund = (wallet: Keypair) =>
+    program.methods
+      .claimRefund()
+      .accountsPartial({
+        curve: curvePda,
+        reserveVault: reserveVaultPda,
+        keyHolder: holderPda(wallet.publicKey),
+        holder: wallet.publicKey,
+        config: configPda,
+      })
+      .signers([wallet])
+      .rpc();
+
+  before(async () => {
+    creator = Keypair.generate();
+    holder = Keypair.generate();
+
+    for (const wallet of [creator, holder]) {
+      await provider.connection.requestAirdrop(
+        wallet.publicKey,
+        10 * anchor.web3.LAMPORTS_PER_SOL
+      );
+    }
+
+    // Wait for airdrop to confirm
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    // Derive PDAs
+    configPda = pda(Buffer.from("config"));
+    curvePda = pda(Buffer.from("curve"), Buffer.from(twitterHandle));
+    reserveVaultPda = pda(Buffer.from("reserve"), curvePda.toBuffer());
+    feeVaultPda = pda(Buffer.from("fee_vault"), curvePda.toBuffer());
+
+    // The config is a singleton, another suite may have created it
+    if ((await provider.connection.getAccountInfo(configPda)) === null) {
+      await program.methods
+        .initialize(
+          provider.wallet.publicKey,
+          provider.wallet.publicKey,
+          provider.wallet.publicKey
+        )
+        .accountsPartial({
+          config: configPda,
+          authority: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    // Create, buy in, activate and sell a few keys before a time-based freeze
+    const launchTs = new anchor.BN((await chainTime()) + 6);
+
+    await program.methods
+      .createCurve(twitterHandle, { profile: {} }, launchTs)
+      .accountsPartial({
+        curve: curvePda,
+        reserveVault: reserveVaultPda,
+        feeVault: feeVaultPda,
+        creator: creator.publicKey,
+        config: configPda,
+        banRecord: pda(Buffer.from("ban"), creator.publicKey.toBuffer()),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .creatorInitialBuy(new anchor.BN(10))
+      .accountsPartial({
+        curve: curvePda,
+        reserveVault: reserveVaultPda,
+        keyHolder: holderPda(creator.publicKey),
+        buyer: creator.publicKey,
+        feeVault: feeVaultPda,
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .activateCurve()
+      .accountsPartial({
+        curve: curvePda,
+        creator: creator.publicKey,
+        config: configPda,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .buyKeys(new anchor.BN(3), new anchor.BN("18446744073709551615"), null)
+      .accountsPartial({
+        curve: curvePda,
+        reserveVault: reserveVaultPda,
+        keyHolder: holderPda(holder.publicKey),
+        buyer: holder.publicKey,
+        feeVault: feeVaultPda,
+        referralCode: null,
+        config: configPda,
+        banRecord: pda(Buffer.from("ban"), holder.publicKey.toBuffer()),
+        botReport: pda(Buffer.from("bot_report"), holder.publicKey.toBuffer()),
+        accessList: pda(Buffer.from("access_list"), curvePda.toBuffer()),
+        accessEntry: pda(Buffer.from("access"), curvePda.toBuffer(), holder.publicKey.toBuffer()),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([holder])
+      .rpc();
+
+    // Wait for launch_ts, then freeze via the permissionless crank (no keeper pool)
+    while ((await chainTime()) < launchTs.toNumber()) {
+      await new Promise(resolve => setTimeout(resolve, 500));
+    }
+
+    await program.methods
+      .crankFreeze()
+      .accountsPartial({
+        curve: curvePda,
+        keeperPool: null,
+        keeper: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const curveAccount = await program.account.bondingCurve.fetch(curvePda);
+    assert.deepEqual(curveAccount.status, { frozen: {} }, "Curve should be frozen");
+    assert.equal(curveAccount.supplyAtFreeze.toString(), "13");
+  });
+
+  it("Rejects refund claims before the refund starts", async () => {
+    try {
+      await claimRefund(holder);
+      assert.fail("Should have thrown an error");
+    } catch (error) {
+      assert.include(error.toString(), "RefundNotActive", "Should throw RefundNotActive error");
+    }
+  });
+
+  it("Only the admin can start a refund before the refund delay", async () => {
+    try {
+      await startRefund(holder);
+      assert.fail("Should have thrown an error");
+    } catch (error) {
+      assert.include(
+        error.toString(),
+        "RefundDeadlineNotReached",
+        "Should throw RefundDeadlineNotReached error"
+      );
+    }
+
+    await startRefund(null);
+
+    const curveAccount = await program.account.bondingCurve.fetch(curvePda);
+    assert.deepEqual(curveAccount.status, { refunding: {} }, "Curve should be refunding");
+  });
+
+  it("Blocks refund claims while claims are paused", async () => {
+    const noPause = { trading: false, creation: false, launches: false, claims: false };
+    const adminAccounts = { config: configPda, authority: provider.wallet.publicKey };
+
+    await program.methods
+      .setPauseFlags({ ...noPause, claims: true })
+      .accountsPartial(adminAccounts)
+      .rpc();
+
+    try {
+      await claimRefund(holder);
+      assert.fail("Should have thrown an error");
+    } catch (error) {
+      assert.include(error.toString(), "ClaimsPaused", "Should throw ClaimsPaused error");
+    } finally {
+      // The config is shared with the other suites
+      await program.methods.setPauseFlags(noPause).accountsPartial(adminAccounts).rpc();
+    }
+  });
+
+  it("Refunds each holder pro-rata and closes their KeyHolder", async () => {
+    const before = await program.account.bondingCurve.fetch(curvePda);
+
+    for (const [wallet, keys] of [[holder, 3], [creator, 10]] as [Keypair, number][]) {
+      const curveAccount = await program.account.bondingCurve.fetch(curvePda);
+      const keyHolderPda = holderPda(wallet.publicKey);
+      const holderRent = await provider.connection.getBalance(keyHolderPda);
+      const walletBefore = await provider.connection.getBalance(wallet.publicKey);
+      const reserveBefore = await provider.connection.getBalance(reserveVaultPda);
+
+      // Mirrors BondingCurve::calculate_refund (rounded down)
+      const refund = before.reserveAtFreeze.muln(keys).div(before.supplyAtFreeze);
+
+      await claimRefund(wallet);
+
+      assert.isNull(
+        await provider.connection.getAccountInfo(keyHolderPda),
+        "KeyHolder should be closed"
+      );
+      assert.equal(
+        reserveBefore - (await provider.connection.getBalance(reserveVaultPda)),
+        refund.toNumber()
+      );
+
+      // The holder pays the fee, so the rent comes back net of it
+      const walletAfter = await provider.connection.getBalance(wallet.publicKey);
+      assert.isAbove(walletAfter - walletBefore, refund.toNumber());
+      assert.isAtMost(walletAfter - walletBefore, refund.toNumber() + holderRent);
+
+      const curveAfter = await program.account.bondingCurve.fetch(curvePda);
+      assert.equal(curveAfter.supply.toString(), curveAccount.supply.subn(keys).toString());
+      assert.equal(
+        curveAfter.reserveBalance.toString(),
+        curveAccount.reserveBalance.sub(refund).toString()
+      );
+    }
+
+    // Every key redeemed, at most rounding dust left behind
+    const curveAccount = await program.account.bondingCurve.fetch(curvePda);
+    assert.equal(curveAccount.supply.toString(), "0");
+    assert.isAtMost(curveAccount.reserveBalance.toNumber(), 1);
+  });
+});
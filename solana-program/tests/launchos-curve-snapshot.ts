@@ -0,0 +1,331 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LaunchosCurve } from "../target/types/launchos_curve";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { keccak_256 } from "@noble/hashes/sha3";
+import { assert } from "chai";
+
+describe("launchos-curve snapshot disputes", () => {
+  // Configure the client to use the local cluster
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.LaunchosCurve as Program<LaunchosCurve>;
+
+  // Mirrors DISPUTE_STAKE_LAMPORTS
+  const DISPUTE_STAKE = 10_000_000;
+
+  // Test accounts
+  let creator: Keypair;
+  let holder: Keypair;
+  let watcher: Keypair;
+  let configPda: PublicKey;
+  let curvePda: PublicKey;
+  let reserveVaultPda: PublicKey;
+  let feeVaultPda: PublicKey;
+  let snapshotPda: PublicKey;
+  let disputePda: PublicKey;
+
+  const twitterHandle = `snap_${Date.now()}`;
+
+  const pda = (...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const chainTime = async () =>
+    provider.connection.getBlockTime(await provider.connection.getSlot());
+
+  const holderPda = (wallet: PublicKey) =>
+    pda(Buffer.from("holder"), curvePda.toBuffer(), wallet.toBuffer());
+
+  const balance = (wallet: PublicKey) => provider.connection.getBalance(wallet);
+
+  // Mirrors snapshot_leaf: keccak(owner || amount as u64 LE)
+  const leaf = (owner: PublicKey, amount: number) =>
+    Buffer.from(
+      keccak_256(
+        Buffer.concat([owner.toBuffer(), new anchor.BN(amount).toArrayLike(Buffer, "le", 8)])
+      )
+    );
+
+  // Mirrors hash_sorted_pair (proofs carry no left/right flags)
+  const hashPair = (a: Buffer, b: Buffer) =>
+    Buffer.from(keccak_256(Buffer.compare(a, b) <= 0 ? Buffer.concat([a, b]) : Buffer.concat([b, a])));
+
+  const createSnapshot = (root: Buffer, signer: Keypair = creator) =>
+    program.methods
+      .createSnapshot(Array.from(root), 2)
+      .accountsPartial({
+        curve: curvePda,
+        snapshot: snapshotPda,
+        builder: pda(Buffer.from("snapshot_builder"), curvePda.toBuffer()),
+        creator: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  const challengeAmount = (owner: PublicKey, amount: number, proof: Buffer[]) =>
+    program.methods
+      .challengeSnapshotAmount(owner, new anchor.BN(amount), proof.map(node => Array.from(node)))
+      .accountsPartial({
+        curve: curvePda,
+        snapshot: snapshotPda,
+        keyHolder: holderPda(owner),
+        challenger: watcher.publicKey,
+      })
+      .signers([watcher])
+      .rpc();
+
+  const answerDispute = (proof: Buffer[]) =>
+    program.methods
+      .answerSnapshotDispute(proof.map(node => Array.from(node)))
+      .accountsPartial({
+        curve: curvePda,
+        snapshot: snapshotPda,
+        keyHolder: holderPda(holder.publicKey),
+        dispute: disputePda,
+        challenger: watcher.publicKey,
+        creator: creator.publicKey,
+      })
+      .rpc();
+
+  before(async () => {
+    creator = Keypair.generate();
+    holder = Keypair.generate();
+    watcher = Keypair.generate();
+
+    for (const wallet of [creator, holder, watcher]) {
+      await provider.connection.requestAirdrop(
+        wallet.publicKey,
+        10 * anchor.web3.LAMPORTS_PER_SOL
+      );
+    }
+
+    // Wait for airdrop to confirm
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    // Derive PDAs
+    configPda = pda(Buffer.from("config"));
+    curvePda = pda(Buffer.from("curve"), Buffer.from(twitterHandle));
+    reserveVaultPda = pda(Buffer.from("reserve"), curvePda.toBuffer());
+    feeVaultPda = pda(Buffer.from("fee_vault"), curvePda.toBuffer());
+    snapshotPda = pda(Buffer.from("snapshot"), curvePda.toBuffer());
+    disputePda = pda(Buffer.from("dispute"), curvePda.toBuffer(), holder.publicKey.toBuffer());
+
+    // The config is a singleton, another suite may have created it
+    if ((await provider.connection.getAccountInfo(configPda)) === null) {
+      await program.methods
+        .initialize(
+          provider.wallet.publicKey,
+          provider.wallet.publicKey,
+          provider.wallet.publicKey
+        )
+        .accountsPartial({
+          config: configPda,
+          authority: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    // Two holders (creator 10 keys, holder 3 keys), then a time-based freeze
+    const launchTs = new anchor.BN((await chainTime()) + 6);
+
+    await program.methods
+      .createCurve(twitterHandle, { profile: {} }, launchTs)
+      .accountsPartial({
+        curve: curvePda,
+        reserveVault: reserveVaultPda,
+        feeVault: feeVaultPda,
+        creator: creator.publicKey,
+        config: configPda,
+        banRecord: pda(Buffer.from("ban"), creator.publicKey.toBuffer()),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .creatorInitialBuy(new anchor.BN(10))
+      .accountsPartial({
+        curve: curvePda,
+        reserveVault: reserveVaultPda,
+        keyHolder: holderPda(creator.publicKey),
+        buyer: creator.publicKey,
+        feeVault: feeVaultPda,
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .activateCurve()
+      .accountsPartial({
+        curve: curvePda,
+        creator: creator.publicKey,
+        config: configPda,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .buyKeys(new anchor.BN(3), new anchor.BN("18446744073709551615"), null)
+      .accountsPartial({
+        curve: curvePda,
+        reserveVault: reserveVaultPda,
+        keyHolder: holderPda(holder.publicKey),
+        buyer: holder.publicKey,
+        feeVault: feeVaultPda,
+        referralCode: null,
+        config: configPda,
+        banRecord: pda(Buffer.from("ban"), holder.publicKey.toBuffer()),
+        botReport: pda(Buffer.from("bot_report"), holder.publicKey.toBuffer()),
+        accessList: pda(Buffer.from("access_list"), curvePda.toBuffer()),
+        accessEntry: pda(Buffer.from("access"), curvePda.toBuffer(), holder.publicKey.toBuffer()),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([holder])
+      .rpc();
+
+    // Wait for launch_ts, then freeze via the permissionless crank (no keeper pool)
+    while ((await chainTime()) < launchTs.toNumber()) {
+      await new Promise(resolve => setTimeout(resolve, 500));
+    }
+
+    await program.methods
+      .crankFreeze()
+      .accountsPartial({
+        curve: curvePda,
+        keeperPool: null,
+        keeper: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const curveAccount = await program.account.bondingCurve.fetch(curvePda);
+    assert.deepEqual(curveAccount.status, { frozen: {} }, "Curve should be frozen");
+  });
+
+  it("Only the creator can submit a root", async () => {
+    const root = hashPair(leaf(creator.publicKey, 10), leaf(holder.publicKey, 3));
+
+    try {
+      await createSnapshot(root, holder);
+      assert.fail("Should have thrown an error");
+    } catch (error) {
+      assert.include(error.toString(), "Unauthorized", "Should throw Unauthorized error");
+    }
+  });
+
+  it("Invalidates a root with a wrong amount", async () => {
+    // Creator inflates their own leaf: 11 keys instead of 10
+    const inflated = leaf(creator.publicKey, 11);
+    const holderLeaf = leaf(holder.publicKey, 3);
+    await createSnapshot(hashPair(inflated, holderLeaf));
+
+    const snapshot = await program.account.snapshot.fetch(snapshotPda);
+    assert.deepEqual(snapshot.status, { challengeable: {} }, "Root should be challengeable");
+
+    // The holder's leaf is correct, so it cannot be challenged
+    try {
+      await challengeAmount(holder.publicKey, 3, [inflated]);
+      assert.fail("Should have thrown an error");
+    } catch (error) {
+      assert.include(error.toString(), "NoAmountMismatch", "Should throw NoAmountMismatch error");
+    }
+
+    // The challenged leaf must be in the root
+    try {
+      await challengeAmount(creator.publicKey, 12, [holderLeaf]);
+      assert.fail("Should have thrown an error");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidMerkleProof", "Should throw InvalidMerkleProof error");
+    }
+
+    const snapshotRent = await balance(snapshotPda);
+    const watcherBefore = await balance(watcher.publicKey);
+
+    await challengeAmount(creator.publicKey, 11, [holderLeaf]);
+
+    assert.isNull(
+      await provider.connection.getAccountInfo(snapshotPda),
+      "Snapshot should be closed"
+    );
+    assert.equal((await balance(watcher.publicKey)) - watcherBefore, snapshotRent);
+
+    const curveAccount = await program.account.bondingCurve.fetch(curvePda);
+    assert.isNull(curveAccount.snapshotRoot, "Root should be cleared");
+  });
+
+  it("Lets the creator answer a membership dispute with a proof", async () => {
+    const creatorLeaf = leaf(creator.publicKey, 10);
+    await createSnapshot(hashPair(creatorLeaf, leaf(holder.publicKey, 3)));
+
+    await program.methods
+      .disputeSnapshotMembership()
+      .accountsPartial({
+        curve: curvePda,
+        snapshot: snapshotPda,
+        keyHolder: holderPda(holder.publicKey),
+        dispute: disputePda,
+        challenger: watcher.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([watcher])
+      .rpc();
+
+    let snapshot = await program.account.snapshot.fetch(snapshotPda);
+    assert.equal(snapshot.openDisputes, 1);
+
+    const dispute = await program.account.snapshotDispute.fetch(disputePda);
+    assert.equal(dispute.holder.toString(), holder.publicKey.toString());
+    assert.equal(dispute.challenger.toString(), watcher.publicKey.toString());
+
+    // The creator gets the response period before the dispute can be upheld
+    try {
+      await program.methods
+        .upholdSnapshotDispute()
+        .accountsPartial({
+          curve: curvePda,
+          snapshot: snapshotPda,
+          dispute: disputePda,
+          challenger: watcher.publicKey,
+        })
+        .rpc();
+      assert.fail("Should have thrown an error");
+    } catch (error) {
+      assert.include(
+        error.toString(),
+        "DisputeResponsePending",
+        "Should throw DisputeResponsePending error"
+      );
+    }
+
+    try {
+      await answerDispute([]);
+      assert.fail("Should have thrown an error");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidMerkleProof", "Should throw InvalidMerkleProof error");
+    }
+
+    const disputeLamports = await balance(disputePda);
+    const creatorBefore = await balance(creator.publicKey);
+    const watcherBefore = await balance(watcher.publicKey);
+
+    await answerDispute([creatorLeaf]);
+
+    // Stake is forfeited to the creator, the rent goes back to the challenger
+    assert.isNull(await provider.connection.getAccountInfo(disputePda), "Dispute should be closed");
+    assert.equal((await balance(creator.publicKey)) - creatorBefore, DISPUTE_STAKE);
+    assert.equal(
+      (await balance(watcher.publicKey)) - watcherBefore,
+      disputeLamports - DISPUTE_STAKE
+    );
+
+    snapshot = await program.account.snapshot.fetch(snapshotPda);
+    assert.equal(snapshot.openDisputes, 0);
+
+    const curveAccount = await program.account.bondingCurve.fetch(curvePda);
+    assert.deepEqual(curveAccount.snapshotRoot, snapshot.merkleRoot, "Root should stand");
+  });
+});
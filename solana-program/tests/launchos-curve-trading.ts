@@ -0,0 +1,701 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LaunchosCurve } from "../target/types/launchos_curve";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+
+describe("launchos-curve trading", () => {
+  // Configure the client to use the local cluster
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.LaunchosCurve as Program<LaunchosCurve>;
+
+  const MAX_U64 = new anchor.BN("18446744073709551615");
+
+  // Test accounts
+  let creator: Keypair;
+  let trader: Keypair;
+  let configPda: PublicKey;
+  let curvePda: PublicKey;
+  let reserveVaultPda: PublicKey;
+  let feeVaultPda: PublicKey;
+
+  const twitterHandle = `trade_${Date.now()}`;
+
+  const pda = (...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const chainTime = async () =>
+    provider.connection.getBlockTime(await provider.connection.getSlot());
+
+  const holderPda = (wallet: PublicKey) =>
+    pda(Buffer.from("holder"), curvePda.toBuffer(), wallet.toBuffer());
+
+  // Read-only quote without a referrer (simulated, nothing is signed)
+  const quote = (side: "buy" | "sell", amount: number) =>
+    program.methods
+      .quote(
+        side === "buy" ? { buy: {} } : { sell: {} },
+        new anchor.BN(amount),
+        { none: {} }
+      )
+      .accountsPartial({
+        curve: curvePda,
+        keyHolder: null,
+      })
+      .view();
+
+  const buyAccounts = (buyer: PublicKey) => ({
+    curve: curvePda,
+    reserveVault: reserveVaultPda,
+    keyHolder: holderPda(buyer),
+    buyer,
+    feeVault: feeVaultPda,
+    referralCode: null,
+    config: configPda,
+    banRecord: pda(Buffer.from("ban"), buyer.toBuffer()),
+    botReport: pda(Buffer.from("bot_report"), buyer.toBuffer()),
+    accessList: pda(Buffer.from("access_list"), curvePda.toBuffer()),
+    accessEntry: pda(Buffer.from("access"), curvePda.toBuffer(), buyer.toBuffer()),
+    systemProgram: SystemProgram.programId,
+  });
+
+  const buyKeys = (
+    buyer: Keypair,
+    amount: number,
+    maxTotalCost: anchor.BN = MAX_U64,
+    deadline: anchor.BN | null = null
+  ) =>
+    program.methods
+      .buyKeys(new anchor.BN(amount), maxTotalCost, deadline)
+      .accountsPartial(buyAccounts(buyer.publicKey))
+      .signers([buyer])
+      .rpc();
+
+  const buyKeysExactIn = (
+    buyer: Keypair,
+    maxLamports: anchor.BN,
+    minKeys: number,
+    deadline: anchor.BN | null = null
+  ) =>
+    program.methods
+      .buyKeysExactIn(maxLamports, new anchor.BN(minKeys), deadline)
+      .accountsPartial(buyAccounts(buyer.publicKey))
+      .signers([buyer])
+      .rpc();
+
+  const sellKeys = (
+    seller: Keypair,
+    amount: number,
+    minPayout: anchor.BN = new anchor.BN(0),
+    deadline: anchor.BN | null = null
+  ) =>
+    program.methods
+      .sellKeys(new anchor.BN(amount), minPayout, deadline)
+      .accountsPartial({
+        curve: curvePda,
+        reserveVault: reserveVaultPda,
+        keyHolder: holderPda(seller.publicKey),
+        seller: seller.publicKey,
+        feeVault: feeVaultPda,
+        referralCode: null,
+        banRecord: pda(Buffer.from("ban"), seller.publicKey.toBuffer()),
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([seller])
+      .rpc();
+
+  const heldKeys = async (wallet: PublicKey) =>
+    (await program.account.keyHolder.fetch(holderPda(wallet))).amount.toNumber();
+
+  before(async () => {
+    creator = Keypair.generate();
+    trader = Keypair.generate();
+
+    for (const wallet of [creator, trader]) {
+      await provider.connection.requestAirdrop(
+        wallet.publicKey,
+        10 * anchor.web3.LAMPORTS_PER_SOL
+      );
+    }
+
+    // Wait for airdrop to confirm
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    // Derive PDAs
+    configPda = pda(Buffer.from("config"));
+    curvePda = pda(Buffer.from("curve"), Buffer.from(twitterHandle));
+    reserveVaultPda = pda(Buffer.from("reserve"), curvePda.toBuffer());
+    feeVaultPda = pda(Buffer.from("fee_vault"), curvePda.toBuffer());
+
+    // The config is a singleton, another suite may have created it
+    if ((await provider.connection.getAccountInfo(configPda)) === null) {
+      await program.methods
+        .initialize(
+          provider.wallet.publicKey,
+          provider.wallet.publicKey,
+          provider.wallet.publicKey
+        )
+        .accountsPartial({
+          config: configPda,
+          authority: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    // Create, buy in and activate without a time-based freeze
+    await program.methods
+      .createCurve(twitterHandle, { profile: {} }, null)
+      .accountsPartial({
+        curve: curvePda,
+        reserveVault: reserveVaultPda,
+        feeVault: feeVaultPda,
+        creator: creator.publicKey,
+        config: configPda,
+        banRecord: pda(Buffer.from("ban"), creator.publicKey.toBuffer()),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .creatorInitialBuy(new anchor.BN(10))
+      .accountsPartial({
+        curve: curvePda,
+        reserveVault: reserveVaultPda,
+        keyHolder: holderPda(creator.publicKey),
+        buyer: creator.publicKey,
+        feeVault: feeVaultPda,
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .activateCurve()
+      .accountsPartial({
+        curve: curvePda,
+        creator: creator.publicKey,
+        config: configPda,
+      })
+      .signers([creator])
+      .rpc();
+
+    // Trader holds a few keys so every sell path has something to sell
+    await buyKeys(trader, 2);
+
+    const curveAccount = await program.account.bondingCurve.fetch(curvePda);
+    assert.deepEqual(curveAccount.status, { active: {} }, "Curve should be active");
+  });
+
+  describe("slippage and deadlines", () => {
+    it("Reverts a buy above max_total_cost", async () => {
+      const cost = new anchor.BN((await quote("buy", 1)).gross.toString());
+
+      try {
+        await buyKeys(trader, 1, cost.subn(1));
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(
+          error.toString(),
+          "SlippageExceeded",
+          "Should throw SlippageExceeded error"
+        );
+      }
+
+      const before = await heldKeys(trader.publicKey);
+      await buyKeys(trader, 1, cost);
+      assert.equal(await heldKeys(trader.publicKey), before + 1);
+    });
+
+    it("Reverts a budget buy below min_keys", async () => {
+      const budget = new anchor.BN((await quote("buy", 2)).gross.toString());
+
+      try {
+        await buyKeysExactIn(trader, budget, 3);
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "BelowMinKeys", "Should throw BelowMinKeys error");
+      }
+
+      const before = await heldKeys(trader.publicKey);
+      await buyKeysExactIn(trader, budget, 2);
+      assert.equal(await heldKeys(trader.publicKey), before + 2);
+    });
+
+    it("Reverts a sell below min_payout", async () => {
+      const payout = new anchor.BN(
+        (await quote("sell", 1)).fees.sell[0].toSeller.toString()
+      );
+
+      try {
+        await sellKeys(trader, 1, payout.addn(1));
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(
+          error.toString(),
+          "PayoutBelowMinimum",
+          "Should throw PayoutBelowMinimum error"
+        );
+      }
+
+      const before = await heldKeys(trader.publicKey);
+      await sellKeys(trader, 1, payout);
+      assert.equal(await heldKeys(trader.publicKey), before - 1);
+    });
+
+    it("Reverts buys and sells after their deadline", async () => {
+      const expired = new anchor.BN((await chainTime()) - 60);
+
+      const attempts: [string, () => Promise<string>][] = [
+        ["buy_keys", () => buyKeys(trader, 1, MAX_U64, expired)],
+        ["buy_keys_exact_in", () => buyKeysExactIn(trader, MAX_U64.shrn(1), 1, expired)],
+        ["sell_keys", () => sellKeys(trader, 1, new anchor.BN(0), expired)],
+      ];
+
+      for (const [name, attempt] of attempts) {
+        try {
+          await attempt();
+          assert.fail(`${name} should have thrown an error`);
+        } catch (error) {
+          assert.include(
+            error.toString(),
+            "DeadlineExceeded",
+            `${name} should throw DeadlineExceeded error`
+          );
+        }
+      }
+
+      // A deadline in the future does not get in the way
+      const later = new anchor.BN((await chainTime()) + 60);
+      const before = await heldKeys(trader.publicKey);
+      await buyKeys(trader, 1, MAX_U64, later);
+      await sellKeys(trader, 1, new anchor.BN(0), later);
+      assert.equal(await heldKeys(trader.publicKey), before);
+    });
+  });
+
+  describe("pause controls", () => {
+    const NO_PAUSE = { trading: false, creation: false, launches: false, claims: false };
+
+    const adminAccounts = () => ({
+      config: configPda,
+      authority: provider.wallet.publicKey,
+    });
+
+    const setPauseFlags = (flags: typeof NO_PAUSE) =>
+      program.methods.setPauseFlags(flags).accountsPartial(adminAccounts()).rpc();
+
+    const setCurvePaused = (paused: boolean) =>
+      program.methods
+        .setCurvePaused(paused)
+        .accountsPartial({ curve: curvePda, ...adminAccounts() })
+        .rpc();
+
+    // The config is shared with the other suites, so always leave it unpaused
+    afterEach(async () => {
+      const config = await program.account.curveConfig.fetch(configPda);
+      if (config.paused) {
+        await program.methods.unpause().accountsPartial(adminAccounts()).rpc();
+      }
+      await setPauseFlags(NO_PAUSE);
+      await setCurvePaused(false);
+    });
+
+    it("Only the admin can pause", async () => {
+      try {
+        await program.methods
+          .pause()
+          .accountsPartial({ config: configPda, authority: trader.publicKey })
+          .signers([trader])
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "Unauthorized", "Should throw Unauthorized error");
+      }
+    });
+
+    it("Blocks buys and sells while globally paused", async () => {
+      await program.methods.pause().accountsPartial(adminAccounts()).rpc();
+
+      for (const attempt of [() => buyKeys(trader, 1), () => sellKeys(trader, 1)]) {
+        try {
+          await attempt();
+          assert.fail("Should have thrown an error");
+        } catch (error) {
+          assert.include(error.toString(), "TradingPaused", "Should throw TradingPaused error");
+        }
+      }
+
+      await program.methods.unpause().accountsPartial(adminAccounts()).rpc();
+
+      const before = await heldKeys(trader.publicKey);
+      await buyKeys(trader, 1);
+      assert.equal(await heldKeys(trader.publicKey), before + 1);
+    });
+
+    it("Keeps pause flags set across unpause", async () => {
+      await setPauseFlags({ ...NO_PAUSE, trading: true });
+      await program.methods.pause().accountsPartial(adminAccounts()).rpc();
+      await program.methods.unpause().accountsPartial(adminAccounts()).rpc();
+
+      try {
+        await buyKeys(trader, 1);
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "TradingPaused", "Should throw TradingPaused error");
+      }
+    });
+
+    it("Blocks curve creation with the creation flag", async () => {
+      await setPauseFlags({ ...NO_PAUSE, creation: true });
+
+      const handle = `paused_${Date.now()}`;
+      const newCurvePda = pda(Buffer.from("curve"), Buffer.from(handle));
+
+      try {
+        await program.methods
+          .createCurve(handle, { profile: {} }, null)
+          .accountsPartial({
+            curve: newCurvePda,
+            reserveVault: pda(Buffer.from("reserve"), newCurvePda.toBuffer()),
+            feeVault: pda(Buffer.from("fee_vault"), newCurvePda.toBuffer()),
+            creator: creator.publicKey,
+            config: configPda,
+            banRecord: pda(Buffer.from("ban"), creator.publicKey.toBuffer()),
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([creator])
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "CreationPaused", "Should throw CreationPaused error");
+      }
+
+      // Trading is a separate group
+      await buyKeys(trader, 1);
+    });
+
+    it("Blocks fee claims with the claims flag", async () => {
+      await setPauseFlags({ ...NO_PAUSE, claims: true });
+
+      try {
+        await program.methods
+          .claimFees({ creator: {} })
+          .accountsPartial({
+            curve: curvePda,
+            feeVault: feeVaultPda,
+            recipientWallet: creator.publicKey,
+            config: configPda,
+          })
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "ClaimsPaused", "Should throw ClaimsPaused error");
+      }
+    });
+
+    it("Pauses a single curve", async () => {
+      await setCurvePaused(true);
+
+      const curveAccount = await program.account.bondingCurve.fetch(curvePda);
+      assert.isTrue(curveAccount.adminPaused, "Curve should be paused");
+
+      for (const attempt of [() => buyKeys(trader, 1), () => sellKeys(trader, 1)]) {
+        try {
+          await attempt();
+          assert.fail("Should have thrown an error");
+        } catch (error) {
+          assert.include(error.toString(), "CurvePaused", "Should throw CurvePaused error");
+        }
+      }
+
+      await setCurvePaused(false);
+
+      const before = await heldKeys(trader.publicKey);
+      await sellKeys(trader, 1);
+      assert.equal(await heldKeys(trader.publicKey), before - 1);
+    });
+  });
+
+  describe("bans", () => {
+    let banListPda: PublicKey;
+
+    const banRecordPda = (wallet: PublicKey) => pda(Buffer.from("ban"), wallet.toBuffer());
+
+    const banAccount = (wallet: PublicKey) =>
+      program.methods
+        .banAccount(wallet, { botActivity: {} })
+        .accountsPartial({
+          banList: banListPda,
+          banRecord: banRecordPda(wallet),
+          authority: provider.wallet.publicKey,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    const unbanAccount = (wallet: PublicKey) =>
+      program.methods
+        .unbanAccount()
+        .accountsPartial({
+          banList: banListPda,
+          banRecord: banRecordPda(wallet),
+          authority: provider.wallet.publicKey,
+          rentReceiver: provider.wallet.publicKey,
+        })
+        .rpc();
+
+    before(async () => {
+      banListPda = pda(Buffer.from("ban_list"));
+
+      // The ban list is a singleton, another suite may have created it
+      if ((await provider.connection.getAccountInfo(banListPda)) === null) {
+        await program.methods
+          .initializeBanList()
+          .accountsPartial({
+            banList: banListPda,
+            authority: provider.wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+    });
+
+    it("Only the ban list authority can ban", async () => {
+      try {
+        await program.methods
+          .banAccount(creator.publicKey, { abuse: {} })
+          .accountsPartial({
+            banList: banListPda,
+            banRecord: banRecordPda(creator.publicKey),
+            authority: trader.publicKey,
+            payer: trader.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([trader])
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "Unauthorized", "Should throw Unauthorized error");
+      }
+    });
+
+    it("Blocks a banned wallet from buying and selling", async () => {
+      await banAccount(trader.publicKey);
+
+      const record = await program.account.banRecord.fetch(banRecordPda(trader.publicKey));
+      assert.equal(record.wallet.toString(), trader.publicKey.toString());
+      assert.deepEqual(record.reason, { botActivity: {} });
+
+      for (const attempt of [() => buyKeys(trader, 1), () => sellKeys(trader, 1)]) {
+        try {
+          await attempt();
+          assert.fail("Should have thrown an error");
+        } catch (error) {
+          assert.include(error.toString(), "AccountBanned", "Should throw AccountBanned error");
+        }
+      }
+    });
+
+    it("Lets an unbanned wallet trade again", async () => {
+      await unbanAccount(trader.publicKey);
+
+      const record = await provider.connection.getAccountInfo(banRecordPda(trader.publicKey));
+      assert.isNull(record, "Ban record should be closed");
+
+      const before = await heldKeys(trader.publicKey);
+      await buyKeys(trader, 1);
+      assert.equal(await heldKeys(trader.publicKey), before + 1);
+    });
+
+    describe("bot reports", () => {
+      // Mirrors REPORT_STAKE_LAMPORTS
+      const REPORT_STAKE = 10_000_000;
+
+      let suspect: Keypair;
+      let reporters: Keypair[];
+      let botReportPda: PublicKey;
+
+      const balance = (wallet: PublicKey) => provider.connection.getBalance(wallet);
+
+      const reportBot = (
+        reporter: Keypair,
+        reported: PublicKey,
+        reason: { botActivity: {} } | { sniping: {} } = { botActivity: {} }
+      ) =>
+        program.methods
+          .reportBot(reported, reason)
+          .accountsPartial({
+            botReport: pda(Buffer.from("bot_report"), reported.toBuffer()),
+            reporter: reporter.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([reporter])
+          .rpc();
+
+      const reporterAccounts = (wallets: Keypair[]) =>
+        wallets.map(wallet => ({ pubkey: wallet.publicKey, isSigner: false, isWritable: true }));
+
+      const confirmBotReport = (order: Keypair[]) =>
+        program.methods
+          .confirmBotReport()
+          .accountsPartial({
+            banList: banListPda,
+            botReport: botReportPda,
+            banRecord: banRecordPda(suspect.publicKey),
+            rentReceiver: reporters[0].publicKey,
+            authority: provider.wallet.publicKey,
+            payer: provider.wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(reporterAccounts(order))
+          .rpc();
+
+      before(async () => {
+        suspect = Keypair.generate();
+        reporters = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+
+        for (const wallet of [suspect, ...reporters]) {
+          await provider.connection.requestAirdrop(
+            wallet.publicKey,
+            2 * anchor.web3.LAMPORTS_PER_SOL
+          );
+        }
+
+        // Wait for airdrop to confirm
+        await new Promise(resolve => setTimeout(resolve, 1000));
+
+        botReportPda = pda(Buffer.from("bot_report"), suspect.publicKey.toBuffer());
+
+        await buyKeys(suspect, 2);
+      });
+
+      it("Rejects self and duplicate reports", async () => {
+        try {
+          await reportBot(suspect, suspect.publicKey);
+          assert.fail("Should have thrown an error");
+        } catch (error) {
+          assert.include(error.toString(), "SelfReport", "Should throw SelfReport error");
+        }
+
+        await reportBot(reporters[0], suspect.publicKey, { sniping: {} });
+
+        try {
+          await reportBot(reporters[0], suspect.publicKey);
+          assert.fail("Should have thrown an error");
+        } catch (error) {
+          assert.include(error.toString(), "DuplicateReport", "Should throw DuplicateReport error");
+        }
+
+        const report = await program.account.botReport.fetch(botReportPda);
+        assert.deepEqual(report.status, { open: {} }, "One report should not flag");
+        assert.equal(report.totalStake.toNumber(), REPORT_STAKE);
+
+        // An open report does not restrict the wallet
+        await buyKeys(suspect, 1);
+      });
+
+      it("Flags at the third report, blocking buys but not sells", async () => {
+        await reportBot(reporters[1], suspect.publicKey);
+        await reportBot(reporters[2], suspect.publicKey);
+
+        const report = await program.account.botReport.fetch(botReportPda);
+        assert.deepEqual(report.status, { flagged: {} }, "Three reports should flag");
+        assert.isNotNull(report.flaggedAt);
+        assert.equal(report.totalStake.toNumber(), 3 * REPORT_STAKE);
+
+        try {
+          await buyKeys(suspect, 1);
+          assert.fail("Should have thrown an error");
+        } catch (error) {
+          assert.include(error.toString(), "AccountFlagged", "Should throw AccountFlagged error");
+        }
+
+        // A wrongly reported wallet can always exit
+        const before = await heldKeys(suspect.publicKey);
+        await sellKeys(suspect, 1);
+        assert.equal(await heldKeys(suspect.publicKey), before - 1);
+      });
+
+      it("Dismissing slashes the stakes and lifts the flag", async () => {
+        const config = await program.account.curveConfig.fetch(configPda);
+        const reportLamports = await balance(botReportPda);
+        const firstReporterBefore = await balance(reporters[0].publicKey);
+
+        await program.methods
+          .dismissBotReport()
+          .accountsPartial({
+            banList: banListPda,
+            botReport: botReportPda,
+            rentReceiver: reporters[0].publicKey,
+            config: configPda,
+            communityWallet: config.communityWallet,
+            authority: provider.wallet.publicKey,
+          })
+          .rpc();
+
+        assert.isNull(
+          await provider.connection.getAccountInfo(botReportPda),
+          "Report should be closed"
+        );
+
+        // Stakes go to the community wallet, only the rent goes back to the first reporter
+        assert.equal(
+          (await balance(reporters[0].publicKey)) - firstReporterBefore,
+          reportLamports - 3 * REPORT_STAKE
+        );
+
+        const before = await heldKeys(suspect.publicKey);
+        await buyKeys(suspect, 1);
+        assert.equal(await heldKeys(suspect.publicKey), before + 1);
+      });
+
+      it("Confirming bans the wallet and refunds the stakes", async () => {
+        await reportBot(reporters[0], suspect.publicKey, { sniping: {} });
+        await reportBot(reporters[1], suspect.publicKey);
+        await reportBot(reporters[2], suspect.publicKey);
+
+        // Reporters must be passed in report order
+        try {
+          await confirmBotReport([reporters[1], reporters[0], reporters[2]]);
+          assert.fail("Should have thrown an error");
+        } catch (error) {
+          assert.include(
+            error.toString(),
+            "InvalidReporterAccounts",
+            "Should throw InvalidReporterAccounts error"
+          );
+        }
+
+        const reportLamports = await balance(botReportPda);
+        const before = await Promise.all(reporters.map(wallet => balance(wallet.publicKey)));
+
+        await confirmBotReport(reporters);
+
+        const after = await Promise.all(reporters.map(wallet => balance(wallet.publicKey)));
+        assert.equal(after[0] - before[0], reportLamports - 2 * REPORT_STAKE, "Stake and rent");
+        assert.equal(after[1] - before[1], REPORT_STAKE, "Stake refunded");
+        assert.equal(after[2] - before[2], REPORT_STAKE, "Stake refunded");
+
+        // The ban takes the first reporter's reason
+        const record = await program.account.banRecord.fetch(banRecordPda(suspect.publicKey));
+        assert.equal(record.wallet.toString(), suspect.publicKey.toString());
+        assert.deepEqual(record.reason, { sniping: {} });
+
+        try {
+          await buyKeys(suspect, 1);
+          assert.fail("Should have thrown an error");
+        } catch (error) {
+          assert.include(error.toString(), "AccountBanned", "Should throw AccountBanned error");
+        }
+
+        await unbanAccount(suspect.publicKey);
+      });
+    });
+  });
+});
@@ -1,8 +1,9 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { LaunchosEscrow } from "../target/types/launchos_escrow";
-import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
-import { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo } from "@solana/spl-token";
+import { LaunchosCurve } from "../target/types/launchos_curve";
+import { PublicKey, Keypair, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo, getAccount } from "@solana/spl-token";
 import { assert } from "chai";
 
 describe("launchos-escrow", () => {
@@ -383,4 +384,308 @@ describe("launchos-escrow", () => {
     const escrowAccount = await program.account.escrowAccount.fetch(escrowPda);
     assert.equal(escrowAccount.totalPools.toNumber(), 3, "Should have 3 pools total");
   });
+
+  describe("multisig authority", () => {
+    // 2-of-3 launchos-curve multisig whose signer PDA becomes the escrow authority
+    const curveProgram = anchor.workspace.LaunchosCurve as Program<LaunchosCurve>;
+    const multisigId = new anchor.BN(1);
+    const signerB = Keypair.generate();
+    const signerC = Keypair.generate();
+
+    let multisigPda: PublicKey;
+    let multisigSigner: PublicKey;
+    let pauseProposal: PublicKey;
+
+    // Propose `ix` from the provider wallet (approval recorded), returning the proposal PDA
+    const propose = async (ix: TransactionInstruction): Promise<PublicKey> => {
+      const multisig = await curveProgram.account.multisig.fetch(multisigPda);
+      const [proposalPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("multisig_proposal"),
+          multisigPda.toBuffer(),
+          multisig.nonce.toArrayLike(Buffer, "le", 8),
+        ],
+        curveProgram.programId
+      );
+
+      await curveProgram.methods
+        .proposeMultisigAction(
+          ix.programId,
+          ix.keys.map((meta) => ({
+            pubkey: meta.pubkey,
+            isSigner: meta.isSigner,
+            isWritable: meta.isWritable,
+          })),
+          ix.data
+        )
+        .accountsPartial({
+          multisig: multisigPda,
+          multisigSigner,
+          proposal: proposalPda,
+          proposer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      return proposalPda;
+    };
+
+    const approve = (proposalPda: PublicKey, signer: Keypair) =>
+      curveProgram.methods
+        .approveMultisigAction()
+        .accountsPartial({
+          multisig: multisigPda,
+          proposal: proposalPda,
+          signer: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+
+    // Target program first, then the proposal's accounts (the signer PDA is signed by the program)
+    const execute = async (proposalPda: PublicKey) => {
+      const proposal = await curveProgram.account.multisigProposal.fetch(proposalPda);
+
+      return curveProgram.methods
+        .executeMultisigAction()
+        .accountsPartial({
+          multisig: multisigPda,
+          proposal: proposalPda,
+          executor: provider.wallet.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: proposal.programId, isSigner: false, isWritable: false },
+          ...proposal.accounts.map((account) => ({
+            pubkey: account.pubkey,
+            isSigner: false,
+            isWritable: account.isWritable,
+          })),
+        ])
+        .rpc();
+    };
+
+    const escrowIx = (paused: boolean) =>
+      (paused ? program.methods.pause() : program.methods.unpause())
+        .accountsPartial({ escrow: escrowPda, authority: multisigSigner })
+        .instruction();
+
+    before(async () => {
+      [multisigPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("multisig"),
+          provider.wallet.publicKey.toBuffer(),
+          multisigId.toArrayLike(Buffer, "le", 8),
+        ],
+        curveProgram.programId
+      );
+      [multisigSigner] = PublicKey.findProgramAddressSync(
+        [Buffer.from("multisig_signer"), multisigPda.toBuffer()],
+        curveProgram.programId
+      );
+
+      await curveProgram.methods
+        .createMultisig(
+          multisigId,
+          [provider.wallet.publicKey, signerB.publicKey, signerC.publicKey],
+          2
+        )
+        .accountsPartial({
+          multisig: multisigPda,
+          multisigSigner,
+          creator: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      // Hand the escrow to the multisig: propose, then accept through an approved proposal
+      const [pendingAuthorityPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pending_authority")],
+        program.programId
+      );
+
+      await program.methods
+        .proposeAuthority(multisigSigner)
+        .accountsPartial({
+          escrow: escrowPda,
+          pending: pendingAuthorityPda,
+          authority: authority.publicKey,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      // Proposing does not move the authority yet
+      let escrowAccount = await program.account.escrowAccount.fetch(escrowPda);
+      assert.equal(escrowAccount.authority.toString(), authority.publicKey.toString());
+
+      // Only the proposed authority can accept
+      try {
+        await program.methods
+          .acceptAuthority()
+          .accountsPartial({
+            escrow: escrowPda,
+            pending: pendingAuthorityPda,
+            newAuthority: authority.publicKey,
+            payer: provider.wallet.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(
+          error.toString(),
+          "NotPendingAuthority",
+          "Should throw NotPendingAuthority error"
+        );
+      }
+
+      const acceptIx = await program.methods
+        .acceptAuthority()
+        .accountsPartial({
+          escrow: escrowPda,
+          pending: pendingAuthorityPda,
+          newAuthority: multisigSigner,
+          payer: provider.wallet.publicKey,
+        })
+        .instruction();
+
+      const acceptProposal = await propose(acceptIx);
+      await approve(acceptProposal, signerB);
+      await execute(acceptProposal);
+
+      // The proposal account is closed on acceptance
+      const pending = await provider.connection.getAccountInfo(pendingAuthorityPda);
+      assert.isNull(pending, "Pending authority should be closed");
+
+      escrowAccount = await program.account.escrowAccount.fetch(escrowPda);
+      assert.equal(escrowAccount.authority.toString(), multisigSigner.toString());
+    });
+
+    it("Rejects execution below the threshold", async () => {
+      pauseProposal = await propose(await escrowIx(true));
+
+      try {
+        await execute(pauseProposal);
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(
+          error.toString(),
+          "MultisigThresholdNotMet",
+          "Should throw MultisigThresholdNotMet error"
+        );
+      }
+
+      const escrowAccount = await program.account.escrowAccount.fetch(escrowPda);
+      assert.equal(escrowAccount.paused, false, "Should not be paused");
+    });
+
+    it("Pauses the escrow once the threshold approves", async () => {
+      await approve(pauseProposal, signerB);
+      await execute(pauseProposal);
+
+      let escrowAccount = await program.account.escrowAccount.fetch(escrowPda);
+      assert.equal(escrowAccount.paused, true, "Should be paused");
+
+      const proposal = await curveProgram.account.multisigProposal.fetch(pauseProposal);
+      assert.equal(proposal.executed, true, "Proposal should be executed");
+
+      // A proposal executes only once
+      try {
+        await execute(pauseProposal);
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "ProposalAlreadyExecuted");
+      }
+
+      // Unpause with a different second signer
+      const unpauseProposal = await propose(await escrowIx(false));
+      await approve(unpauseProposal, signerC);
+      await execute(unpauseProposal);
+
+      escrowAccount = await program.account.escrowAccount.fetch(escrowPda);
+      assert.equal(escrowAccount.paused, false, "Should be unpaused");
+    });
+
+    it("Withdraws from a pool through an approved proposal", async () => {
+      const withdrawAmount = new anchor.BN(10_000000); // 10 USDC
+      const poolBefore = await program.account.pool.fetch(poolPda);
+
+      const recipientTokenAccount = await createAccount(
+        provider.connection,
+        authority,
+        usdcMint,
+        authority.publicKey
+      );
+
+      const withdrawIx = await program.methods
+        .withdraw(withdrawAmount)
+        .accountsPartial({
+          escrow: escrowPda,
+          pool: poolPda,
+          authority: multisigSigner,
+          poolTokenAccount: poolTokenAccount,
+          recipientTokenAccount: recipientTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .instruction();
+
+      const proposalPda = await propose(withdrawIx);
+      await approve(proposalPda, signerB);
+      await execute(proposalPda);
+
+      const poolAccount = await program.account.pool.fetch(poolPda);
+      assert.equal(
+        poolAccount.balance.toNumber(),
+        poolBefore.balance.toNumber() - withdrawAmount.toNumber(),
+        "Pool balance should drop by the withdrawal"
+      );
+
+      const recipient = await getAccount(provider.connection, recipientTokenAccount);
+      assert.equal(
+        Number(recipient.amount),
+        withdrawAmount.toNumber(),
+        "Recipient should receive the withdrawal"
+      );
+    });
+
+    it("Rejects proposals made against a replaced signer set", async () => {
+      const staleProposal = await propose(await escrowIx(true));
+
+      // Rotate the signer set (same members, new owner_set_seqno)
+      const rotateIx = await curveProgram.methods
+        .setMultisigSigners(
+          [provider.wallet.publicKey, signerB.publicKey, signerC.publicKey],
+          2
+        )
+        .accountsPartial({
+          multisig: multisigPda,
+          multisigSigner,
+        })
+        .instruction();
+
+      const rotateProposal = await propose(rotateIx);
+      await approve(rotateProposal, signerB);
+      await execute(rotateProposal);
+
+      const multisig = await curveProgram.account.multisig.fetch(multisigPda);
+      assert.equal(multisig.ownerSetSeqno, 1, "Signer set should be replaced");
+
+      try {
+        await approve(staleProposal, signerB);
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "ProposalStale", "Should throw ProposalStale error");
+      }
+
+      try {
+        await execute(staleProposal);
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "ProposalStale", "Should throw ProposalStale error");
+      }
+
+      const escrowAccount = await program.account.escrowAccount.fetch(escrowPda);
+      assert.equal(escrowAccount.paused, false, "Stale proposal should not pause");
+    });
+  });
 });